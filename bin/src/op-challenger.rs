@@ -3,15 +3,16 @@
 use anyhow::{anyhow, Result};
 use clap::{ArgAction, Parser};
 use ethers::{
-    prelude::{Address, Provider, SignerMiddleware, Ws},
+    prelude::{Address, Provider, SignerMiddleware, Ws, U256},
     providers::Http,
     signers::LocalWallet,
 };
 use op_challenger_driver::{
-    DisputeFactoryDriver, Driver, DriverConfig, FaultGameWatcherDriver, GlobalState,
-    TxDispatchDriver,
+    ConfirmationDepth, Confirmations, DisputeFactoryDriver, Driver, DriverConfig,
+    FaultGameWatcherDriver, GameResolutionDriver, GameType, GasBumpPolicy, MemoryStore,
+    OutputProposalDriver, SledStore, StateStore, TxDispatchDriver, TxJournal,
 };
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc, time::Duration};
 use tokio::{sync::Mutex, task::JoinSet};
 use tracing::Level;
 
@@ -41,13 +42,15 @@ struct Args {
     )]
     trusted_op_node_endpoint: String,
 
-    /// The private key used for signing transactions.
+    /// The private key used for signing transactions. Optional in dry-run mode, where no
+    /// transactions are sent.
     #[arg(
         long,
         help = "The private key used for signing transactions.",
+        required_unless_present = "dry_run",
         env = "OP_CHALLENGER_KEY"
     )]
-    signer_key: String,
+    signer_key: Option<String>,
 
     /// The address of the dispute game factory contract.
     #[arg(
@@ -64,6 +67,123 @@ struct Args {
         env = "OP_CHALLENGER_L2OO"
     )]
     l2_output_oracle: Address,
+
+    /// The type of the games created to challenge invalid output proposals: `fault`,
+    /// `output-attestation` or a number.
+    /// Creating `fault` games is refused until their root claim commits to the output trace.
+    #[arg(
+        long,
+        help = "The type of the games created to challenge invalid output proposals: `fault`, `output-attestation` or a number.",
+        default_value = "fault",
+        env = "OP_CHALLENGER_GAME_TYPE"
+    )]
+    game_type: GameType,
+
+    /// The path of the database that games are persisted to. If not set, games are only kept in
+    /// memory and are forgotten on restart.
+    #[arg(
+        long,
+        help = "The path of the database that games are persisted to.",
+        env = "OP_CHALLENGER_DB_PATH"
+    )]
+    db_path: Option<PathBuf>,
+
+    /// The L1 block to backfill `DisputeGameCreated` and `OutputProposed` events from. The
+    /// cursors from a previous run are resumed from instead, if they are ahead of the start block.
+    #[arg(
+        long,
+        help = "The L1 block to backfill DisputeGameCreated and OutputProposed events from.",
+        env = "OP_CHALLENGER_START_BLOCK"
+    )]
+    start_block: Option<u64>,
+
+    /// The confirmation depth of `DisputeGameCreated` events: a number of blocks,
+    /// `latest`, `safe` or `finalized`.
+    #[arg(
+        long,
+        help = "The confirmation depth of DisputeGameCreated events: a number of blocks, `latest`, `safe` or `finalized`.",
+        default_value = "0",
+        env = "OP_CHALLENGER_FACTORY_CONFIRMATIONS"
+    )]
+    factory_confirmations: ConfirmationDepth,
+
+    /// The confirmation depth of the L1 block that claim data is read at: a number of blocks,
+    /// `latest`, `safe` or `finalized`.
+    #[arg(
+        long,
+        help = "The confirmation depth of the L1 block that claim data is read at: a number of blocks, `latest`, `safe` or `finalized`.",
+        default_value = "0",
+        env = "OP_CHALLENGER_WATCHER_CONFIRMATIONS"
+    )]
+    watcher_confirmations: ConfirmationDepth,
+
+    /// The confirmation depth of `OutputProposed` events: a number of blocks,
+    /// `latest`, `safe` or `finalized`.
+    #[arg(
+        long,
+        help = "The confirmation depth of OutputProposed events: a number of blocks, `latest`, `safe` or `finalized`.",
+        default_value = "0",
+        env = "OP_CHALLENGER_OUTPUT_CONFIRMATIONS"
+    )]
+    output_confirmations: ConfirmationDepth,
+
+    /// The interval, in seconds, at which every game is checked for new claims. New claims are
+    /// usually picked up from `Move` events on every new L1 head.
+    #[arg(
+        long,
+        help = "The interval, in seconds, at which every game is checked for new claims.",
+        default_value = "300",
+        value_parser = clap::value_parser!(u64).range(1..),
+        env = "OP_CHALLENGER_WATCHER_POLL_INTERVAL"
+    )]
+    watcher_poll_interval: u64,
+
+    /// The time, in seconds, after which a pending transaction is replaced with bumped fees.
+    #[arg(
+        long,
+        help = "The time, in seconds, after which a pending transaction is replaced with bumped fees.",
+        default_value = "36",
+        env = "OP_CHALLENGER_TX_BUMP_AFTER"
+    )]
+    tx_bump_after: u64,
+
+    /// The percentage that the fees of a stuck transaction are bumped by. Must be at least 10%,
+    /// the minimum that nodes accept for a replacement.
+    #[arg(
+        long,
+        help = "The percentage that the fees of a stuck transaction are bumped by.",
+        default_value = "20",
+        value_parser = clap::value_parser!(u64).range(10..),
+        env = "OP_CHALLENGER_TX_BUMP_PERCENT"
+    )]
+    tx_bump_percent: u64,
+
+    /// The maximum fee per gas, in gwei, that a transaction is ever sent with.
+    #[arg(
+        long,
+        help = "The maximum fee per gas, in gwei, that a transaction is ever sent with.",
+        default_value = "500",
+        env = "OP_CHALLENGER_TX_MAX_FEE_GWEI"
+    )]
+    tx_max_fee_gwei: u64,
+
+    /// Runs the drivers without ever sending a transaction. Transactions are simulated with
+    /// `eth_call` and recorded in the journal at `--journal-path` instead.
+    #[arg(
+        long,
+        help = "Simulate and journal transactions instead of sending them.",
+        env = "OP_CHALLENGER_DRY_RUN"
+    )]
+    dry_run: bool,
+
+    /// The path of the journal that simulated transactions are appended to in dry-run mode.
+    #[arg(
+        long,
+        help = "The path of the journal that simulated transactions are appended to in dry-run mode.",
+        default_value = "dry-run-journal.jsonl",
+        env = "OP_CHALLENGER_JOURNAL_PATH"
+    )]
+    journal_path: PathBuf,
 }
 
 #[tokio::main]
@@ -76,17 +196,48 @@ async fn main() -> Result<()> {
         signer_key,
         dispute_game_factory,
         l2_output_oracle,
+        game_type,
+        db_path,
+        start_block,
+        factory_confirmations,
+        watcher_confirmations,
+        output_confirmations,
+        watcher_poll_interval,
+        tx_bump_after,
+        tx_bump_percent,
+        tx_max_fee_gwei,
+        dry_run,
+        journal_path,
     } = Args::parse();
 
     // Initialize the tracing subscriber
     init_tracing_subscriber(v)?;
 
+    // Without a signer key, sign with a throwaway wallet. This is only allowed in dry-run mode,
+    // where nothing is sent, so the provider is effectively read-only.
+    let signer = match (signer_key, dry_run) {
+        (Some(key), _) => key.parse::<LocalWallet>()?,
+        (None, true) => {
+            tracing::warn!(target: "op-challenger-cli", "No signer key set; using a random wallet for the read-only provider.");
+            LocalWallet::new(&mut ethers::core::rand::thread_rng())
+        }
+        (None, false) => anyhow::bail!("A signer key is required unless --dry-run is set"),
+    };
+
+    // Open the journal of simulated transactions in dry-run mode.
+    let journal = if dry_run {
+        tracing::warn!(target: "op-challenger-cli", "Dry-run mode enabled; transactions will be journaled to {} instead of being sent.", journal_path.display());
+        Some(TxJournal::open(&journal_path)?)
+    } else {
+        None
+    };
+
     // Connect to the websocket endpoint.
     tracing::debug!(target: "op-challenger-cli", "Connecting to websocket endpoint...");
     let l1_endpoint = Arc::new(
         SignerMiddleware::new_with_provider_chain(
             Provider::<Ws>::connect(&l1_ws_endpoint).await?,
-            signer_key.parse::<LocalWallet>()?,
+            signer,
         )
         .await?,
     );
@@ -97,14 +248,40 @@ async fn main() -> Result<()> {
     let node_endpoint = Arc::new(Provider::<Http>::try_from(&trusted_op_node_endpoint)?);
     tracing::info!(target: "op-challenger-cli", "Node connected successfully @ {}", &trusted_op_node_endpoint);
 
+    // Open the storage backend and load the persisted global state.
+    let store: Arc<dyn StateStore> = match &db_path {
+        Some(path) => Arc::new(SledStore::open(path)?),
+        None => {
+            tracing::warn!(target: "op-challenger-cli", "No database path set; games will not be persisted.");
+            Arc::new(MemoryStore)
+        }
+    };
+    let global_state = store.load()?.unwrap_or_default();
+    tracing::info!(target: "op-challenger-cli", "Loaded {} persisted games (last L1 block: {:?}).", global_state.alphabet_games.len(), global_state.last_l1_block);
+    let global_state = Arc::new(Mutex::new(global_state));
+
     // Create the driver config.
     let driver_config = Arc::new(DriverConfig::new(
         l1_endpoint,
         node_endpoint,
         dispute_game_factory,
         l2_output_oracle,
+        game_type,
+        store,
+        start_block,
+        Confirmations {
+            dispute_factory: factory_confirmations,
+            fault_game_watcher: watcher_confirmations,
+            output_proposals: output_confirmations,
+        },
+        Duration::from_secs(watcher_poll_interval),
+        GasBumpPolicy {
+            bump_after: Duration::from_secs(tx_bump_after),
+            bump_percent: tx_bump_percent,
+            max_fee_per_gas: U256::from(tx_max_fee_gwei) * U256::exp10(9),
+        },
+        journal,
     ));
-    let global_state = Arc::new(Mutex::new(GlobalState::default()));
     tracing::info!(target: "op-challenger-cli", "Driver config created successfully.");
 
     // Creates a new driver stack and starts the driver loops.
@@ -131,6 +308,8 @@ async fn main() -> Result<()> {
         TxDispatchDriver,
         DisputeFactoryDriver,
         FaultGameWatcherDriver,
+        GameResolutionDriver,
+        OutputProposalDriver,
     );
 
     Ok(())
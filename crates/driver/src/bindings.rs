@@ -4,8 +4,11 @@ use ethers::prelude::abigen;
 abigen!(
     DisputeGame_Factory,
     r"[
-        event DisputeGameCreated(address indexed, uint8 indexed, bytes32 indexed)
+        event DisputeGameCreated(address indexed disputeProxy, uint8 indexed gameType, bytes32 indexed rootClaim)
+        error GameAlreadyExists(bytes32 uuid)
+        error NoImplementation(uint8 gameType)
         function create(uint8 gameType, bytes32 rootClaim, bytes calldata extraData) external returns (address _proxy)
+        function gameImpls(uint8 gameType) external view returns (address)
     ]"
 );
 
@@ -13,7 +16,7 @@ abigen!(
 abigen!(
     L2OutputOracle,
     r"[
-        event OutputProposed(bytes32 indexed, uint256 indexed, uint256 indexed, uint256)
+        event OutputProposed(bytes32 indexed outputRoot, uint256 indexed l2OutputIndex, uint256 indexed l2BlockNumber, uint256 l1Timestamp)
     ]"
 );
 
@@ -21,13 +24,149 @@ abigen!(
 abigen!(
     FaultDisputeGame,
     r"[
+        event Move(uint256 indexed parentIndex, bytes32 indexed pivot, address indexed claimant)
+        error GameNotInProgress()
+        error ClockTimeExceeded()
+        error ClockNotExpired()
+        error CannotDefendRootClaim()
+        error ClaimAlreadyExists()
+        error GameDepthExceeded()
+        error InvalidParent()
+        error InvalidPrestate()
+        error ValidStep()
         function attack(uint256 _parentIndex, bytes32 _pivot) external payable
         function defend(uint256 _parentIndex, bytes32 _pivot) external payable
         function claimData(uint256 _index) external view returns ((uint32,bool,bytes32,uint128,uint128))
+        function claimDataLen() external view returns (uint256 len_)
+        function version() external view returns (string)
         function step(uint256 _stateIndex, uint256 _claimIndex, bool _isAttack, bytes calldata _stateData, bytes calldata _proof) external
         function resolve() external returns (uint8)
+        function status() external view returns (uint8)
         function rootClaim() external pure returns (bytes32)
         function createdAt() external view returns (uint64)
         function l2BlockNumber() external view returns (uint256)
+        function MAX_GAME_DEPTH() external view returns (uint256)
+        function ABSOLUTE_PRESTATE() external view returns (bytes32)
+        function GAME_DURATION() external view returns (uint64)
     ]"
 );
+
+// Generates the bindings for the `claimData` getter of `FaultDisputeGame` contracts using the
+// [ClaimDataLayout::V1](crate::ClaimDataLayout::V1) layout, which tracks the claimant and bond of
+// each claim.
+abigen!(
+    FaultDisputeGameV1,
+    r"[
+        function claimData(uint256 _index) external view returns (uint32 parentIndex, address counteredBy, address claimant, uint128 bond, bytes32 claim, uint128 position, uint128 clock)
+    ]"
+);
+
+// Generates the bindings for the `Multicall3` contract.
+abigen!(
+    Multicall3,
+    r"[
+        struct Call3 { address target; bool allowFailure; bytes callData; }
+        struct Result { bool success; bytes returnData; }
+        function aggregate3(Call3[] calldata calls) external payable returns (Result[] memory returnData)
+    ]"
+);
+
+#[cfg(test)]
+mod test {
+    use super::{DisputeGameCreatedFilter, OutputProposedFilter};
+    use ethers::{
+        contract::parse_log,
+        types::{Address, Log, H256, U256},
+    };
+
+    /// A synthetic `OutputProposed` log in the format returned by `eth_getLogs`. It was not
+    /// captured from a live network: only the event signature in `topics[0]` is real, and the
+    /// other fields are made up, with each indexed field holding a distinct value.
+    ///
+    /// TODO: Replace this fixture and [DISPUTE_GAME_CREATED_LOG] with logs recorded from a devnet
+    /// or a public network. Until then, the tests only catch fields that are decoded out of
+    /// order, not mistakes in the ABI of the events themselves.
+    const OUTPUT_PROPOSED_LOG: &str = r#"{
+        "address": "0xdfe97868233d1aa22e815a266982f2cf17685a27",
+        "topics": [
+            "0xa7aaf2512769da4e444e3de247be2564225c2e7a8f74cfe528e46e17d24868e2",
+            "0x6f0ba8a5a3f9d5b6c1e4e0a7c58b0c8a9e1e8e3d2b67f4a0b1c2d3e4f5a6b7c8",
+            "0x00000000000000000000000000000000000000000000000000000000000001a4",
+            "0x000000000000000000000000000000000000000000000000000000000645c277"
+        ],
+        "data": "0x0000000000000000000000000000000000000000000000000000000065006d20",
+        "blockNumber": "0x11a2c3b",
+        "transactionHash": "0x3c1f5e8b0a7d2e4f6a8c9b1d3e5f7a9c0b2d4e6f8a1c3e5b7d9f0a2c4e6b8d0f",
+        "transactionIndex": "0x5e",
+        "blockHash": "0x8e4a2c0f6b1d3e5a7c9f0b2d4e6a8c1e3f5b7d9a0c2e4f6b8d1a3c5e7f9b0d2a",
+        "logIndex": "0xd3",
+        "removed": false
+    }"#;
+
+    /// A synthetic `DisputeGameCreated` log in the format returned by `eth_getLogs`. Like
+    /// [OUTPUT_PROPOSED_LOG], only its event signature is real.
+    const DISPUTE_GAME_CREATED_LOG: &str = r#"{
+        "address": "0xe5965ab5962edc7477c8520243a95517cd252fa9",
+        "topics": [
+            "0xfad0599ff449d8d9685eadecca8cb9e00924c5fd8367c1c09469824939e1ffec",
+            "0x000000000000000000000000a1b2c3d4e5f60718293a4b5c6d7e8f9012345678",
+            "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "0x03c7a1f0b2d4e6f8a0c2e4f6b8d0a2c4e6f8b0d2a4c6e8f0b2d4a6c8e0f2b4d6"
+        ],
+        "data": "0x",
+        "blockNumber": "0x11a2c40",
+        "transactionHash": "0x5a7c9e1b3d5f7a9c1e3b5d7f9a1c3e5b7d9f1a3c5e7b9d1f3a5c7e9b1d3f5a7c",
+        "transactionIndex": "0x12",
+        "blockHash": "0x2b4d6f8a0c2e4b6d8f0a2c4e6b8d0f2a4c6e8b0d2f4a6c8e0b2d4f6a8c0e2b4d",
+        "logIndex": "0x40",
+        "removed": false
+    }"#;
+
+    #[test]
+    fn decodes_output_proposed() {
+        let log: Log = serde_json::from_str(OUTPUT_PROPOSED_LOG).unwrap();
+        let event = parse_log::<OutputProposedFilter>(log).unwrap();
+        assert_eq!(
+            H256(event.output_root),
+            "0x6f0ba8a5a3f9d5b6c1e4e0a7c58b0c8a9e1e8e3d2b67f4a0b1c2d3e4f5a6b7c8"
+                .parse::<H256>()
+                .unwrap()
+        );
+        assert_eq!(event.l_2_output_index, U256::from(420));
+        assert_eq!(event.l_2_block_number, U256::from(105_235_063));
+        assert_eq!(event.l_1_timestamp, U256::from(1_694_526_752));
+    }
+
+    #[test]
+    fn decodes_dispute_game_created() {
+        let log: Log = serde_json::from_str(DISPUTE_GAME_CREATED_LOG).unwrap();
+        let event = parse_log::<DisputeGameCreatedFilter>(log).unwrap();
+        assert_eq!(
+            event.dispute_proxy,
+            "0xa1b2c3d4e5f60718293a4b5c6d7e8f9012345678"
+                .parse::<Address>()
+                .unwrap()
+        );
+        assert_eq!(event.game_type, 0);
+        assert_eq!(
+            H256(event.root_claim),
+            "0x03c7a1f0b2d4e6f8a0c2e4f6b8d0a2c4e6f8b0d2a4c6e8f0b2d4a6c8e0f2b4d6"
+                .parse::<H256>()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_logs() {
+        let output_proposed: Log = serde_json::from_str(OUTPUT_PROPOSED_LOG).unwrap();
+        assert!(parse_log::<DisputeGameCreatedFilter>(output_proposed).is_err());
+
+        let dispute_game_created: Log = serde_json::from_str(DISPUTE_GAME_CREATED_LOG).unwrap();
+        assert!(parse_log::<OutputProposedFilter>(dispute_game_created.clone()).is_err());
+
+        // A log with a missing indexed field can not be decoded.
+        let mut truncated = dispute_game_created;
+        truncated.topics.pop();
+        assert!(parse_log::<DisputeGameCreatedFilter>(truncated).is_err());
+    }
+}
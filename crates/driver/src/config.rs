@@ -1,12 +1,19 @@
 //! The `config` module contains the [DriverConfig].
 
-use crate::SignerMiddlewareWS;
+use crate::{
+    Confirmations, GameType, GasBumpPolicy, SignerMiddlewareWS, StateStore, TxJournal, TxMeta,
+    TxOutcome, TxQueue, TxRequest,
+};
 use ethers::{
     providers::{Http, Provider},
     types::{transaction::eip2718::TypedTransaction, Address},
 };
-use std::sync::Arc;
-use tokio::sync::{mpsc, Mutex};
+use std::{sync::Arc, time::Duration};
+use tokio::sync::oneshot;
+
+/// The maximum number of transactions waiting to be sent by the
+/// [TxDispatchDriver](crate::TxDispatchDriver).
+const TX_QUEUE_CAPACITY: usize = 128;
 
 /// The [DriverConfig] struct contains the configuration for the [Driver](crate::Driver) implementations.
 pub struct DriverConfig {
@@ -20,30 +27,78 @@ pub struct DriverConfig {
     pub dispute_game_factory: Address,
     /// The address of the L2OutputOracle contract.
     pub l2_output_oracle: Address,
-    /// The sending handle of the MPSC channel used to send transactions.
-    pub tx_sender: mpsc::Sender<TypedTransaction>,
-    /// The receiving handle of the MPSC channel used to send transactions.
-    pub tx_receiver: Mutex<mpsc::Receiver<TypedTransaction>>,
+    /// The type of the games created to challenge invalid output proposals.
+    pub game_type: GameType,
+    /// The queue of transactions to be sent by the [TxDispatchDriver](crate::TxDispatchDriver),
+    /// ordered by urgency.
+    pub tx_queue: TxQueue,
+    /// The storage backend that the [GlobalState](crate::GlobalState) is persisted to.
+    pub store: Arc<dyn StateStore>,
+    /// The L1 block to backfill `DisputeGameCreated` and `OutputProposed` events from. The
+    /// persisted cursor of each event is resumed from instead, if it is ahead of the start block.
+    pub start_block: Option<u64>,
+    /// The confirmation depths that the drivers wait for before acting on L1 events.
+    pub confirmations: Confirmations,
+    /// The interval at which the [FaultGameWatcherDriver](crate::FaultGameWatcherDriver) syncs
+    /// every game, in case an event was missed.
+    pub watcher_poll_interval: Duration,
+    /// The policy for replacing stuck transactions sent by the
+    /// [TxDispatchDriver](crate::TxDispatchDriver).
+    pub gas_bump_policy: GasBumpPolicy,
+    /// The journal that transactions are recorded to instead of being sent. If set, the
+    /// [TxDispatchDriver](crate::TxDispatchDriver) runs in dry-run mode, and only simulates
+    /// transactions.
+    pub journal: Option<TxJournal>,
 }
 
 impl DriverConfig {
     /// Creates a new [DriverConfig] with the given configuration.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         l1_provider: Arc<SignerMiddlewareWS>,
         node_provider: Arc<Provider<Http>>,
         dispute_game_factory: Address,
         l2_output_oracle: Address,
+        game_type: GameType,
+        store: Arc<dyn StateStore>,
+        start_block: Option<u64>,
+        confirmations: Confirmations,
+        watcher_poll_interval: Duration,
+        gas_bump_policy: GasBumpPolicy,
+        journal: Option<TxJournal>,
     ) -> Self {
-        // Create a new MPSC channel for sending transactions from the drivers.
-        let (tx_sender, tx_receiver) = mpsc::channel(128);
-
         Self {
             l1_provider,
             node_provider,
             dispute_game_factory,
             l2_output_oracle,
-            tx_sender,
-            tx_receiver: Mutex::new(tx_receiver),
+            game_type,
+            tx_queue: TxQueue::new(TX_QUEUE_CAPACITY),
+            store,
+            start_block,
+            confirmations,
+            watcher_poll_interval,
+            gas_bump_policy,
+            journal,
         }
     }
+
+    /// Queues the given transaction to be sent by the [TxDispatchDriver](crate::TxDispatchDriver),
+    /// waiting for capacity if the queue is full.
+    ///
+    /// ### Takes
+    /// - `tx`: The transaction to send.
+    /// - `meta`: The metadata that the transaction is ordered by.
+    ///
+    /// ### Returns
+    /// - The receiving handle of the [TxOutcome] of the transaction.
+    pub async fn dispatch(
+        &self,
+        tx: TypedTransaction,
+        meta: TxMeta,
+    ) -> oneshot::Receiver<TxOutcome> {
+        let (request, outcome) = TxRequest::new(tx, meta);
+        self.tx_queue.push(request).await;
+        outcome
+    }
 }
@@ -1,26 +1,54 @@
 //! The `driver` module contains implementations of the [Driver] trait.
 
 use crate::{
-    bindings::{DisputeGame_Factory, FaultDisputeGame},
+    bindings::{
+        fault_dispute_game, fault_dispute_game_v1, DisputeGameCreatedFilter, DisputeGame_Factory,
+        FaultDisputeGame, L2OutputOracle, MoveFilter,
+    },
+    handlers,
+    multicall::{self, BatchCaller, Call},
+    tx_manager::{TxManager, MAX_TX_ATTEMPTS},
     types::GameType,
-    Driver, DriverConfig, GlobalState,
+    utils, ClaimDataLayout, Driver, DriverConfig, GlobalState, L1BlockRef, RevertAction,
+    SharedGame, SignerMiddlewareWS, TxKind, TxMeta, TxOutcome, TxRevert,
 };
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use ethers::{
-    providers::{Middleware, StreamExt},
-    types::{Address, H256, U256},
+    contract::{parse_log, EthEvent},
+    providers::{Middleware, MiddlewareError, StreamExt},
+    types::{
+        transaction::eip2718::TypedTransaction, Address, BlockId, BlockNumber, Bytes, Filter, Log,
+        H256, U256,
+    },
 };
-use op_challenger_solvers::fault::{AlphabetGame, ClaimData, Clock, FaultGame, Response};
-use std::{cmp::Ordering, sync::Arc, time::Duration};
-use tokio::sync::Mutex;
+use op_challenger_solvers::fault::{
+    expires_at, providers::AlphabetTraceProvider, AlphabetGame, Claim, ClaimData, Clock, FaultGame,
+    GameStatus, Position, Resolution, Response, MAX_SUPPORTED_DEPTH,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    ops::RangeInclusive,
+    sync::Arc,
+    time::Duration,
+};
+use tokio::sync::{oneshot, Mutex};
 
-/// The trace for the alphabet game.
+/// The trace for the alphabet game. Padded by the [AlphabetGame] up to `2^MAX_GAME_DEPTH` states.
 /// TODO: Delete this.
 const TRACE: [u8; 16] = [
     16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31,
 ];
 
+/// The interval at which the [TxDispatchDriver] checks on its pending transactions. This is the
+/// L1 block time.
+const TX_CHECK_INTERVAL: Duration = Duration::from_secs(12);
+
+/// The storage slot holding the length of the claim data array of `FaultDisputeGame`s that
+/// predate the `claimDataLen()` getter.
+const V0_CLAIM_DATA_LEN_SLOT: u64 = 1;
+
 /// Defines a new [Driver] implementation.
 #[macro_export]
 macro_rules! define_driver {
@@ -55,49 +83,85 @@ define_driver!(
     TxDispatchDriver,
     (|self: TxDispatchDriver| {
         async move {
-            tracing::info!(target: "tx-dispatch-driver", "Starting transaction dispatch driver...");
-            let mut locked_receive_ch = self.config.tx_receiver.lock().await;
-            tracing::info!(target: "tx-dispatch-driver", "Locked receive channel mutex successfully. Beginning tx dispatch loop.");
-
-            while let Some(mut tx) = locked_receive_ch.recv().await {
-                tracing::info!(target: "tx-dispatch-driver", "Transaction dispatch request received in dispatch driver. Sending transaction...");
-
-                // TODO: Check the mempool and simulate the transaction prior to sending it.
-                match self.config.l1_provider.estimate_gas(&tx, None).await {
-                    Ok(gas) => {
-                        tracing::info!(target: "tx-dispatch-driver", "Transaction simulation successful. Gas estimate: {}", gas);
-                        tx.set_gas(gas);
-                        tx.set_gas_price(
-                            self.config
-                                .l1_provider
-                                .get_gas_price()
-                                .await
-                                .unwrap_or(U256::one())
-                                * 2,
-                        );
+            tracing::info!(target: "tx-dispatch-driver", "Starting transaction dispatch driver. Beginning tx dispatch loop.");
+
+            // In dry-run mode, transactions are only simulated and journaled.
+            if let Some(journal) = &self.config.journal {
+                tracing::warn!(target: "tx-dispatch-driver", "Running in dry-run mode. Transactions are simulated and journaled, but never sent.");
+                loop {
+                    let request = self.config.tx_queue.pop().await;
+                    let simulation = self.simulate(&request.tx).await;
+                    match &simulation {
+                        Ok(_) => {
+                            tracing::info!(target: "tx-dispatch-driver", "Simulated transaction successfully (dry run): {:?}", request.meta);
+                        }
+                        Err(reason) => {
+                            tracing::warn!(target: "tx-dispatch-driver", "Simulated transaction reverted with reason {} (dry run): {:?}", reason, request.meta);
+                        }
                     }
-                    Err(err) => {
-                        tracing::error!(target: "tx-dispatch-driver", "Error simulating transaction: {}", err);
-                        continue;
+                    if let Err(e) = journal.record(&request, &simulation) {
+                        tracing::error!(target: "tx-dispatch-driver", "Error recording transaction in the journal: {}", e);
                     }
+                    let _ = request.outcome.send(TxOutcome::Simulated(simulation));
                 }
+            }
+
+            let mut tx_manager = TxManager::new(
+                Arc::clone(&self.config.l1_provider),
+                self.config.gas_bump_policy,
+            );
+            let mut check = tokio::time::interval(TX_CHECK_INTERVAL);
 
-                match self.config.l1_provider.send_transaction(tx, None).await {
-                    Ok(res) => {
-                        tracing::info!(target: "tx-dispatch-driver", "Transaction sent successfully. Tx hash: {}", res.tx_hash());
+            loop {
+                tokio::select! {
+                    // Send the most urgent queued transaction.
+                    request = self.config.tx_queue.pop() => {
+                        tracing::info!(target: "tx-dispatch-driver", "Transaction dispatch request received in dispatch driver: {:?}. Sending transaction...", request.meta);
+
+                        match tx_manager.send(request).await {
+                            Ok(tx_hash) => {
+                                tracing::info!(target: "tx-dispatch-driver", "Transaction sent successfully. Tx hash: {:?}", tx_hash);
+                            }
+                            Err(e) => {
+                                // Soft failure, log the error and continue.
+                                tracing::error!(target: "tx-dispatch-driver", "Error sending transaction: {}", e);
+                            }
+                        }
                     }
-                    Err(e) => {
-                        // Soft failure, log the error and continue.
-                        tracing::error!(target: "tx-dispatch-driver", "Error sending transaction: {}", e);
+                    // Track the pending transactions until they are included, replacing the
+                    // ones that are stuck.
+                    _ = check.tick() => {
+                        if let Err(e) = tx_manager.check_pending().await {
+                            tracing::error!(target: "tx-dispatch-driver", "Error checking pending transactions: {}", e);
+                        }
+                        tracing::debug!(target: "tx-dispatch-driver", "Transaction queue metrics: {:?}", self.config.tx_queue.metrics());
                     }
                 }
             }
-
-            Ok(())
         }
     })
 );
 
+impl TxDispatchDriver {
+    /// Simulates the given transaction with `eth_call` against the latest L1 block.
+    ///
+    /// ### Takes
+    /// - `tx`: The transaction to simulate.
+    ///
+    /// ### Returns
+    /// - `Ok(Bytes)`: The return data of the simulated transaction.
+    /// - `Err(String)`: The revert reason of the simulated transaction, or the error that
+    ///   prevented it from being simulated.
+    async fn simulate(&self, tx: &TypedTransaction) -> std::result::Result<Bytes, String> {
+        self.config.l1_provider.call(tx, None).await.map_err(|e| {
+            match e.as_error_response().and_then(|e| e.as_revert_data()) {
+                Some(data) => TxRevert::decode(&data).to_string(),
+                None => e.to_string(),
+            }
+        })
+    }
+}
+
 define_driver!(
     DisputeFactoryDriver,
     (|self: DisputeFactoryDriver| {
@@ -108,192 +172,1305 @@ define_driver!(
                 self.config.dispute_game_factory,
                 Arc::clone(&self.config.l1_provider),
             );
-            let mut stream = self
-                .config
-                .l1_provider
-                .subscribe_logs(&factory.dispute_game_created_filter().filter)
-                .await?;
-
-            tracing::info!(target: "dispute-factory-driver", "Subscribed to DisputeGameCreated events, beginning event loop.");
-            while let Some(dispute_game_created) = stream.next().await {
-                tracing::debug!(target: "dispute-factory-driver", "DisputeGameCreated event received");
-
-                // The DisputeGameCreated event contains a `gameType` field, which is a `GameType`.
-                let game_type_raw = dispute_game_created.topics.get(2).ok_or(anyhow::anyhow!(
-                    "Critical failure: `gameType` field not present in `DisputeGameCreated` event."
-                ))?;
-                // A [GameType] will always be a u8, so we can safely index the last byte in the
-                // topic.
-                let game_type_u8 = game_type_raw[31];
-                // The address of the created dispute game proxy.
-                let game_addr: Address = Address::from_slice(&dispute_game_created.topics.get(1).ok_or(anyhow::anyhow!(
-                    "Critical failure: `disputeProxy` field not present in `DisputeGameCreated` event."
-                ))?[12..]);
-
-                // Attempt to dispatch the proper response based on the game type.
-                if let Ok(game_type) = GameType::try_from(game_type_u8) {
-                    match game_type {
-                        GameType::Fault => {
-                            tracing::info!(target: "dispute-factory-driver", "New Fault game created at address {}. Fetching root claim data...", game_addr);
-
-                            // Fetch the root claim data.
-                            let game = FaultDisputeGame::new(
-                                game_addr,
-                                Arc::clone(&self.config.l1_provider),
-                            );
-                            let created_at = game.created_at().await?;
-
-                            // TODO: Global state is entirely in memory, this won't do. We need to
-                            // persist games to a local database and load them on startup. In
-                            // addition, it'd be great to get a reverse sync mechanism going so
-                            // that games that are not locally stored can be fetched and existing
-                            // ongoing games can be updated.
-                            tracing::info!(target: "dispute-factory-driver", "Fetched root claim data successfully. Locking global state mutex and pushing new game...");
-                            let mut state = self.state.lock().await;
-                            state.alphabet_games.push(AlphabetGame {
-                                address: game_addr,
-                                created_at,
-                                state: Vec::default(),
-                                trace: Arc::new(TRACE),
-                            });
-                            tracing::info!(target: "dispute-factory-driver", "Pushed new game successfully. Forwarding dispatch to the fault game driver...");
-                        }
-                        GameType::Validity => {
-                            tracing::error!(target: "dispute-factory-driver", "DisputeGameCreated event contained a `Validity` game type, which is not yet supported");
-                        }
-                        GameType::OutputAttestation => {
-                            tracing::error!(target: "dispute-factory-driver", "DisputeGameCreated event contained a `OutputAttestation` game type, which is not yet supported");
-                        }
-                    }
-                } else {
-                    tracing::error!(target: "dispute-factory-driver", "DisputeGameCreated event contained an unknown game type: {}", game_type_u8);
-                    continue;
+            let filter = factory.dispute_game_created_filter().filter;
+
+            // Stop tracking games that were reorged out while we were down. Their cursor is
+            // rolled back, so the backfill picks up the canonical events in their place.
+            let reorged =
+                utils::remove_reorged_games(&self.config.l1_provider, &self.state).await?;
+            if !reorged.is_empty() {
+                tracing::warn!(target: "dispute-factory-driver", "Creation of games {:?} was reorged out. No longer tracking them.", reorged);
+                if let Err(e) = self.config.store.save(&*self.state.lock().await) {
+                    tracing::error!(target: "dispute-factory-driver", "Failed to persist global state: {}", e);
                 }
             }
 
-            Ok(())
+            let this = &self;
+            utils::index_events(
+                &self.config,
+                &self.state,
+                "DisputeGameCreated",
+                &filter,
+                |state| &mut state.last_l1_block,
+                |log| this.handle_dispute_game_created(log),
+            )
+            .await
         }
     })
 );
 
+impl DisputeFactoryDriver {
+    /// Handles a `DisputeGameCreated` event, adding the created game to the [GlobalState] if it
+    /// is not already being tracked.
+    async fn handle_dispute_game_created(&self, dispute_game_created: Log) -> Result<()> {
+        tracing::debug!(target: "dispute-factory-driver", "DisputeGameCreated event received");
+
+        // Decode the event with its typed binding, so that its fields match the ABI.
+        let event = parse_log::<DisputeGameCreatedFilter>(dispute_game_created.clone())?;
+        let game_type_u8 = event.game_type;
+        // The address of the created dispute game proxy.
+        let game_addr = event.dispute_proxy;
+
+        let origin = L1BlockRef::from_log(&dispute_game_created);
+
+        // Wait for the event to be confirmed before acting on it. Removals are always processed
+        // immediately.
+        if dispute_game_created.removed != Some(true)
+            && !utils::wait_for_confirmation(
+                &self.config.l1_provider,
+                self.config.confirmations.dispute_factory,
+                &dispute_game_created,
+            )
+            .await?
+        {
+            tracing::warn!(target: "dispute-factory-driver", "DisputeGameCreated event for game at address {} was reorged out before it was confirmed. Ignoring it.", game_addr);
+            return Ok(());
+        }
+
+        {
+            let mut state = self.state.lock().await;
+
+            // If the event was reorged out, stop tracking the game it created.
+            if dispute_game_created.removed == Some(true) {
+                if origin.is_some()
+                    && state.game_origins.get(&game_addr) == origin.as_ref()
+                    && state.remove_game(game_addr)
+                {
+                    tracing::warn!(target: "dispute-factory-driver", "Creation of game at address {} was reorged out. No longer tracking it.", game_addr);
+                    if let Err(e) = self.config.store.save(&state) {
+                        tracing::error!(target: "dispute-factory-driver", "Failed to persist global state: {}", e);
+                    }
+                }
+                return Ok(());
+            }
+
+            // The same event may be seen again when resuming from the persisted cursor, or in a
+            // different block after a reorg.
+            if state.alphabet_games.contains_key(&game_addr) {
+                tracing::debug!(target: "dispute-factory-driver", "Game at address {} is already being tracked", game_addr);
+                if let Some(origin) = origin {
+                    if state.game_origins.insert(game_addr, origin) != Some(origin) {
+                        if let Err(e) = self.config.store.save(&state) {
+                            tracing::error!(target: "dispute-factory-driver", "Failed to persist global state: {}", e);
+                        }
+                    }
+                }
+                return Ok(());
+            }
+        }
+
+        // Attempt to dispatch the proper response based on the game type.
+        if let Ok(game_type) = GameType::try_from(game_type_u8) {
+            match game_type {
+                GameType::Fault => {
+                    tracing::info!(target: "dispute-factory-driver", "New Fault game created at address {}. Fetching game parameters...", game_addr);
+
+                    // Fetch the game parameters.
+                    let game =
+                        FaultDisputeGame::new(game_addr, Arc::clone(&self.config.l1_provider));
+                    let created_at = game.created_at().await?;
+                    let max_game_depth = game.max_game_depth().await?;
+                    let absolute_prestate = game.absolute_prestate().await?;
+                    let game_duration = game.game_duration().await?;
+
+                    // Refuse to play games with parameters that the solver does not
+                    // support.
+                    let alphabet_game = match Self::new_alphabet_game(
+                        game_addr,
+                        created_at,
+                        max_game_depth,
+                        absolute_prestate.into(),
+                        game_duration,
+                    )
+                    .await
+                    {
+                        Ok(alphabet_game) => alphabet_game,
+                        Err(e) => {
+                            tracing::error!(target: "dispute-factory-driver", "Refusing to play Fault game at address {}: {}", game_addr, e);
+                            return Ok(());
+                        }
+                    };
+
+                    tracing::info!(target: "dispute-factory-driver", "Fetched game parameters successfully (max depth: {}, game duration: {}s). Locking global state mutex and pushing new game...", alphabet_game.max_depth, alphabet_game.game_duration);
+                    let mut state = self.state.lock().await;
+                    let saved = self.config.store.save_game(&alphabet_game);
+                    state
+                        .alphabet_games
+                        .insert(game_addr, Arc::new(Mutex::new(alphabet_game)));
+                    if let Some(origin) = origin {
+                        state.game_origins.insert(game_addr, origin);
+                    }
+                    if let Err(e) = saved.and_then(|_| self.config.store.save(&state)) {
+                        tracing::error!(target: "dispute-factory-driver", "Failed to persist global state: {}", e);
+                    }
+                    tracing::info!(target: "dispute-factory-driver", "Pushed new game successfully. Forwarding dispatch to the fault game driver...");
+                }
+                GameType::Validity => {
+                    tracing::error!(target: "dispute-factory-driver", "DisputeGameCreated event contained a `Validity` game type, which is not yet supported");
+                }
+                GameType::OutputAttestation => {
+                    tracing::error!(target: "dispute-factory-driver", "DisputeGameCreated event contained a `OutputAttestation` game type, which is not yet supported");
+                }
+            }
+        } else {
+            tracing::error!(target: "dispute-factory-driver", "DisputeGameCreated event contained an unknown game type: {}", game_type_u8);
+        }
+
+        Ok(())
+    }
+
+    /// Creates a new [AlphabetGame] from the onchain parameters of a `FaultDisputeGame`.
+    async fn new_alphabet_game(
+        address: Address,
+        created_at: u64,
+        max_game_depth: U256,
+        absolute_prestate: Claim,
+        game_duration: u64,
+    ) -> Result<AlphabetGame> {
+        let provider = Self::alphabet_trace_provider(max_game_depth)?;
+        AlphabetGame::new(
+            address,
+            created_at,
+            provider.max_depth,
+            absolute_prestate,
+            game_duration,
+            provider,
+        )
+        .await
+    }
+
+    /// Creates the [AlphabetTraceProvider] for a `FaultDisputeGame` with the given onchain max
+    /// depth, refusing depths beyond [MAX_SUPPORTED_DEPTH].
+    fn alphabet_trace_provider(max_game_depth: U256) -> Result<AlphabetTraceProvider> {
+        let max_depth = u64::try_from(max_game_depth)
+            .ok()
+            .filter(|depth| *depth <= MAX_SUPPORTED_DEPTH)
+            .ok_or(anyhow::anyhow!(
+                "Unsupported max depth {}; the maximum supported depth is {}",
+                max_game_depth,
+                MAX_SUPPORTED_DEPTH
+            ))?;
+        AlphabetTraceProvider::new(Arc::new(TRACE), max_depth)
+    }
+}
+
 // Whole thing's scuffed, mocking it out.
 define_driver!(
     FaultGameWatcherDriver,
     (|self: FaultGameWatcherDriver| {
         async move {
+            tracing::info!(target: "fault-game-watcher", "Subscribing to new L1 heads...");
+            let mut heads = self.config.l1_provider.subscribe_blocks().await?;
+            let mut fallback = tokio::time::interval(self.config.watcher_poll_interval);
+            let mut synced_to: Option<u64> = None;
+
             loop {
-                tracing::info!(target: "fault-game-watcher", "Checking for updates in ongoing FaultDisputeGames...");
+                tokio::select! {
+                    // Periodically sync every game, in case a `Move` event was missed.
+                    _ = fallback.tick() => {
+                        tracing::info!(target: "fault-game-watcher", "Checking for updates in ongoing FaultDisputeGames...");
+                        let block = utils::confirmed_l1_block(
+                            &self.config.l1_provider,
+                            self.config.confirmations.fault_game_watcher,
+                        )
+                        .await?;
+                        self.sync_games(block, None).await?;
+                        synced_to = Some(block.number);
+                        tracing::debug!(target: "fault-game-watcher", "Done checking for updates. Next full check in {:?}.", self.config.watcher_poll_interval);
+                    }
+                    // On every new head, only sync the games that have had moves made in the
+                    // newly confirmed blocks.
+                    head = heads.next() => {
+                        if head.is_none() {
+                            anyhow::bail!("L1 head subscription closed");
+                        }
+                        let block = utils::confirmed_l1_block(
+                            &self.config.l1_provider,
+                            self.config.confirmations.fault_game_watcher,
+                        )
+                        .await?;
+                        let Some(blocks) = Self::unsynced_blocks(synced_to, block.number) else {
+                            continue;
+                        };
+
+                        let moved = self.moved_games(*blocks.start(), *blocks.end()).await?;
+                        if !moved.is_empty() {
+                            tracing::info!(target: "fault-game-watcher", "Syncing {} games with new claims in L1 blocks {:?}...", moved.len(), blocks);
+                            self.sync_games(block, Some(&moved)).await?;
+                        }
+                        synced_to = Some(block.number);
+                    }
+                }
+            }
+        }
+    })
+);
+
+impl FaultGameWatcherDriver {
+    /// Syncs the claim data of the tracked games with their onchain claim data as of the given
+    /// L1 block, and dispatches responses to new claims.
+    ///
+    /// ### Takes
+    /// - `block`: The confirmed L1 block to read the claim data at.
+    /// - `games`: The addresses of the games to sync. If `None`, every game is synced, and games
+    ///   whose creation was reorged out are removed.
+    async fn sync_games(&self, block: L1BlockRef, games: Option<&HashSet<Address>>) -> Result<()> {
+        // Stop tracking games whose creation was reorged out.
+        if games.is_none() {
+            let reorged =
+                utils::remove_reorged_games(&self.config.l1_provider, &self.state).await?;
+            if !reorged.is_empty() {
+                tracing::warn!(target: "fault-game-watcher", "Creation of games {:?} was reorged out. No longer tracking them.", reorged);
+                if let Err(e) = self.config.store.save(&*self.state.lock().await) {
+                    tracing::error!(target: "fault-game-watcher", "Failed to persist global state: {}", e);
+                }
+            }
+        }
+
+        // Read all claim data at the same confirmed L1 block, so that it can be checked for
+        // reorgs before any response to it is dispatched.
+        let block_id = BlockId::Hash(block.hash);
+        let batch = BatchCaller::new(Arc::clone(&self.config.l1_provider), block_id).await?;
+
+        // Collect the games to sync. The lock on the global state is released before the games
+        // are locked, so that other drivers are not blocked while the games are synced.
+        let tracked = self.state.lock().await.games();
+        let mut in_progress = Vec::with_capacity(tracked.len());
+        for (address, game) in tracked {
+            if games.is_none_or(|games| games.contains(&address))
+                && game.lock().await.status == GameStatus::InProgress
+            {
+                in_progress.push((address, game));
+            }
+        }
+
+        // Fetch the length of the claim data array in each game, and the layout of its elements.
+        let addresses = in_progress
+            .iter()
+            .map(|(address, _)| *address)
+            .collect::<Vec<_>>();
+        let metadata = Self::fetch_metadata(&batch, &addresses).await?;
+
+        // Sync the games concurrently. A game that fails to sync does not stop the others from
+        // being synced.
+        let batch = &batch;
+        futures::future::join_all(in_progress.iter().filter_map(|(address, game)| {
+            let (length, layout) = *metadata.get(address)?;
+            Some(async move {
+                if let Err(e) = self.sync_game(game, batch, block, length, layout).await {
+                    tracing::error!(target: "fault-game-watcher", "Failed to sync game at address {}: {}", address, e);
+                }
+            })
+        }))
+        .await;
+        Ok(())
+    }
+
+    /// Syncs the claim data of a single game with its onchain claim data, and dispatches
+    /// responses to new claims. Only the lock of the game is held while it is synced.
+    ///
+    /// ### Takes
+    /// - `game`: The game to sync.
+    /// - `batch`: The [BatchCaller] reading at the confirmed L1 `block`.
+    /// - `block`: The confirmed L1 block that the claim data is read at.
+    /// - `length`: The length of the game's onchain claim data array.
+    /// - `layout`: The [ClaimDataLayout] of the game's onchain claim data.
+    async fn sync_game(
+        &self,
+        game: &SharedGame,
+        batch: &BatchCaller,
+        block: L1BlockRef,
+        length: usize,
+        layout: ClaimDataLayout,
+    ) -> Result<()> {
+        let shared = Arc::clone(game);
+        let mut game = game.lock().await;
+        let contract = FaultDisputeGame::new(game.address, Arc::clone(&self.config.l1_provider));
+
+        // The claim data array is append-only, so the local claim data must be a
+        // prefix of the onchain claim data. Any local claims past the common prefix
+        // were reorged out.
+        let mut common_len = game.state.len().min(length);
+        while common_len > 0 {
+            let onchain =
+                Self::fetch_claims(batch, game.address, layout, common_len - 1..common_len).await?;
+            if Self::is_same_claim(&onchain[0], &game.state[common_len - 1]) {
+                break;
+            }
+            common_len -= 1;
+        }
+        if common_len < game.state.len() {
+            tracing::warn!(target: "fault-game-watcher", "{} claims in game at address {} were reorged out. Re-syncing from index {}...", game.state.len() - common_len, game.address, common_len);
+            game.state.truncate(common_len);
+        }
+
+        // Steps counter claims at the maximum depth without adding a claim or emitting an event,
+        // so the `countered` flags of the existing claims are refreshed on every sync.
+        Self::refresh_countered(batch, &mut game, layout).await?;
+
+        let local_len = game.state.len();
+        if length > local_len {
+            tracing::info!(target: "fault-game-watcher", "New claim data found in game at address {}. Fetching...", game.address);
+
+            // Add the new claims to the local state and process them in-order.
+            let claims = Self::fetch_claims(batch, game.address, layout, local_len..length).await?;
+            for (i, claim_data) in (local_len..length).zip(claims) {
+                game.state.push(claim_data);
+
+                // TODO(perf): We can be smarter about which claims we respond to. Fetch
+                // the full state and only respond to claims that need a counter
+                // from us. Maybe a `respond_to_all` function within the `FaultGame` trait
+                // would be useful to hide this logic from the driver.
+                let Some((tx, meta)) = Self::plan_response(&contract, &game, i).await else {
+                    continue;
+                };
+
+                // Never dispatch a response to claims that were read from a block
+                // that has since been reorged out. The claims are discarded, and
+                // re-synced on the next check.
+                if !utils::is_canonical(&self.config.l1_provider, &block).await? {
+                    tracing::warn!(target: "fault-game-watcher", "L1 block {} was reorged out while processing game at address {}. Discarding claims read from it...", block.number, game.address);
+                    game.state.truncate(local_len);
+                    break;
+                }
+                let outcome = self.config.dispatch(tx, meta).await;
+                tracing::info!(target: "fault-game-watcher", "Dispatched response to claim at index={} for game at address {}", i, game.address);
+                tokio::spawn(Self::track_response(
+                    Arc::clone(&self.config),
+                    Arc::clone(&self.state),
+                    Arc::clone(&shared),
+                    i,
+                    outcome,
+                ));
+            }
+        } else {
+            tracing::debug!(target: "fault-game-watcher", "No new claim data found in game at address {}", game.address);
+        }
+
+        // Report the predicted outcome of the game, assuming no further moves are made.
+        match (game.resolution(), game.expected_status().await) {
+            (Ok(resolution), Ok(expected_status)) => {
+                tracing::info!(target: "fault-game-watcher", "Game at address {} is predicted to resolve as {:?} (winning: {}). Resolvable at timestamp {}.", game.address, resolution.status, resolution.status == expected_status, resolution.resolvable_at);
+            }
+            (Err(e), _) | (_, Err(e)) => {
+                tracing::error!(target: "fault-game-watcher", "Failed to predict the outcome of game at address {}: {}", game.address, e);
+            }
+        }
+
+        // Persist the updated claim data.
+        if let Err(e) = self
+            .state
+            .lock()
+            .await
+            .save_game(self.config.store.as_ref(), &game)
+        {
+            tracing::error!(target: "fault-game-watcher", "Failed to persist game at address {}: {}", game.address, e);
+        }
+        Ok(())
+    }
+
+    /// Plans the response to the claim at the given index, returning the transaction to dispatch
+    /// and its metadata if the claim needs a response. The response is due before the claim can
+    /// no longer be countered.
+    async fn plan_response(
+        contract: &FaultDisputeGame<SignerMiddlewareWS>,
+        game: &AlphabetGame,
+        index: usize,
+    ) -> Option<(TypedTransaction, TxMeta)> {
+        let deadline = expires_at(game.state(), index, game.game_duration());
+        let meta = |kind| TxMeta::new(kind).game(game.address).deadline(deadline);
+        match game.respond(index).await {
+            Ok(Response::Move(is_attack, claim, _)) => {
+                tracing::debug!(target: "fault-game-watcher", "Dispatching move against claim at index={} for game at address {}", index, game.address);
+                // We never need to respond to a secondary move because the
+                // claims are processed in-order.
+                let call = if is_attack {
+                    contract.attack(index.into(), claim.into())
+                } else {
+                    contract.defend(index.into(), claim.into())
+                };
+                Some((call.tx, meta(TxKind::Move)))
+            }
+            Ok(Response::Step(state_index, parent_index, is_attack, state_data, proof)) => Some((
+                contract
+                    .step(
+                        state_index.into(),
+                        parent_index.into(),
+                        is_attack,
+                        state_data,
+                        proof,
+                    )
+                    .tx,
+                meta(TxKind::Step),
+            )),
+            Ok(Response::DoNothing) => {
+                tracing::debug!(target: "fault-game-watcher", "No response to new claim (index: {}) at address {}", index, game.address);
+                None
+            }
+            Err(e) => {
+                tracing::error!(target: "fault-game-watcher", "Failed to formulate response to new claim data: {}", e);
+                None
+            }
+        }
+    }
 
-                let mut global_state = self.state.lock().await;
-                for game in global_state.alphabet_games.iter_mut() {
+    /// Waits for the [TxOutcome] of the response to the claim at the given index, re-planning
+    /// and dispatching the response again against the current local state if it was not
+    /// included. See [FaultGameWatcherDriver::retry_response].
+    async fn track_response(
+        config: Arc<DriverConfig>,
+        state: Arc<Mutex<GlobalState>>,
+        game: SharedGame,
+        index: usize,
+        outcome: oneshot::Receiver<TxOutcome>,
+    ) {
+        let address = game.lock().await.address;
+        Self::retry_response(&state, address, index, outcome, || {
+            let config = Arc::clone(&config);
+            let game = Arc::clone(&game);
+            async move {
+                // Re-plan the response. The claim may have been reorged out, or the game
+                // resolved, in the meantime.
+                let (tx, meta) = {
+                    let game = game.lock().await;
+                    if game.state.len() <= index || game.status != GameStatus::InProgress {
+                        return None;
+                    }
+                    let contract =
+                        FaultDisputeGame::new(game.address, Arc::clone(&config.l1_provider));
+                    Self::plan_response(&contract, &game, index).await?
+                };
+                Some(config.dispatch(tx, meta).await)
+            }
+        })
+        .await
+    }
+
+    /// Waits for the [TxOutcome] of the response to the claim at the given index. If the response
+    /// was not included, it is re-planned and dispatched again with `replan`, up to
+    /// [MAX_TX_ATTEMPTS] times in total. Responses that reverted with a known error are not
+    /// retried; instead, the [RevertAction] of the error is taken.
+    ///
+    /// ### Takes
+    /// - `state`: The global state, in which games to re-sync are marked as stale.
+    /// - `address`: The address of the game.
+    /// - `index`: The index of the claim that is responded to.
+    /// - `outcome`: The receiving handle of the [TxOutcome] of the first attempt.
+    /// - `replan`: Re-plans and dispatches the response, returning the receiving handle of its
+    ///   [TxOutcome], or `None` if the response is no longer needed.
+    async fn retry_response<F, Fut>(
+        state: &Mutex<GlobalState>,
+        address: Address,
+        index: usize,
+        mut outcome: oneshot::Receiver<TxOutcome>,
+        mut replan: F,
+    ) where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Option<oneshot::Receiver<TxOutcome>>>,
+    {
+        for attempt in 1..=MAX_TX_ATTEMPTS {
+            match outcome.await {
+                Ok(outcome) if outcome.is_final() => {
+                    tracing::info!(target: "fault-game-watcher", "Response to claim at index={} for game at address {} {}", index, address, outcome);
+                    return;
+                }
+                Ok(outcome) => match outcome.revert().map(TxRevert::action) {
+                    Some(RevertAction::Drop) => {
+                        tracing::warn!(target: "fault-game-watcher", "Response to claim at index={} for game at address {} {}. The response is no longer needed.", index, address, outcome);
+                        return;
+                    }
+                    Some(RevertAction::Resync) => {
+                        tracing::warn!(target: "fault-game-watcher", "Response to claim at index={} for game at address {} {}. Re-syncing the game without retrying the response...", index, address, outcome);
+                        state.lock().await.stale_games.insert(address);
+                        return;
+                    }
+                    Some(RevertAction::Alert) => {
+                        tracing::error!(target: "fault-game-watcher", "Response to claim at index={} for game at address {} {}. The response can not succeed; not retrying.", index, address, outcome);
+                        return;
+                    }
+                    None => {
+                        tracing::warn!(target: "fault-game-watcher", "Response to claim at index={} for game at address {} {} (attempt {}/{})", index, address, outcome, attempt, MAX_TX_ATTEMPTS);
+                    }
+                },
+                Err(_) => {
+                    tracing::warn!(target: "fault-game-watcher", "Outcome of response to claim at index={} for game at address {} is unknown (attempt {}/{})", index, address, attempt, MAX_TX_ATTEMPTS);
+                }
+            }
+            if attempt == MAX_TX_ATTEMPTS {
+                tracing::error!(target: "fault-game-watcher", "Giving up on responding to claim at index={} for game at address {}", index, address);
+                return;
+            }
+
+            outcome = match replan().await {
+                Some(outcome) => outcome,
+                None => return,
+            };
+        }
+    }
+
+    /// Returns the addresses of the tracked games that need to be synced after the block range
+    /// `[from, to]`: games that have had `Move` events emitted in the range, new games that have
+    /// not been synced yet, games that were marked as stale, and games that may have been stepped
+    /// against.
+    async fn moved_games(&self, from: u64, to: u64) -> Result<HashSet<Address>> {
+        let mut addresses = Vec::new();
+        let mut unsynced = HashSet::new();
+        let (tracked, stale) = {
+            let mut state = self.state.lock().await;
+            (state.games(), std::mem::take(&mut state.stale_games))
+        };
+        for (address, game) in tracked {
+            match Self::sync_required(address, &*game.lock().await, &stale) {
+                Some(true) => {
+                    addresses.push(address);
+                    unsynced.insert(address);
+                }
+                Some(false) => addresses.push(address),
+                None => {}
+            }
+        }
+        if addresses.is_empty() {
+            return Ok(unsynced);
+        }
+
+        let filter = Filter::new()
+            .address(addresses)
+            .topic0(MoveFilter::signature())
+            .from_block(from)
+            .to_block(to);
+        unsynced.extend(
+            self.config
+                .l1_provider
+                .get_logs(&filter)
+                .await?
+                .into_iter()
+                .map(|log| log.address),
+        );
+        Ok(unsynced)
+    }
+
+    /// Returns the range of confirmed L1 blocks that have not been checked for `Move` events yet.
+    ///
+    /// ### Takes
+    /// - `synced_to`: The last L1 block that every game was synced at, if any. Until the first
+    ///   full sync by the polling fallback, no blocks are checked.
+    /// - `confirmed`: The number of the latest confirmed L1 block.
+    ///
+    /// ### Returns
+    /// - The block range to check, or `None` if there are no new confirmed blocks.
+    fn unsynced_blocks(synced_to: Option<u64>, confirmed: u64) -> Option<RangeInclusive<u64>> {
+        let from = synced_to? + 1;
+        (from <= confirmed).then_some(from..=confirmed)
+    }
+
+    /// Returns whether the given game needs to be synced after new L1 blocks are confirmed.
+    ///
+    /// ### Takes
+    /// - `address`: The address of the game.
+    /// - `game`: The game.
+    /// - `stale`: The games that were marked as stale.
+    ///
+    /// ### Returns
+    /// - `None` if the game is no longer in progress.
+    /// - `Some(true)` if the game must be synced, because it has not been synced yet, was marked
+    ///   as stale, or has uncountered claims at the maximum depth. Steps against those claims do
+    ///   not emit an event, so they can only be observed by syncing the game.
+    /// - `Some(false)` if the game only needs to be synced if `Move` events were emitted in it.
+    fn sync_required(
+        address: Address,
+        game: &AlphabetGame,
+        stale: &HashSet<Address>,
+    ) -> Option<bool> {
+        (game.status == GameStatus::InProgress).then(|| {
+            game.state.is_empty()
+                || stale.contains(&address)
+                || Self::steppable_claims(game).next().is_some()
+        })
+    }
+
+    /// Returns the indices of the uncountered claims at the maximum depth of the game, which can
+    /// still be countered by a step.
+    fn steppable_claims(game: &AlphabetGame) -> impl Iterator<Item = usize> + '_ {
+        game.state
+            .iter()
+            .enumerate()
+            .filter(|(_, claim_data)| {
+                !claim_data.countered && claim_data.position.depth() == game.max_depth()
+            })
+            .map(|(index, _)| index)
+    }
+
+    /// Refreshes the `countered` flags of the game's uncountered claims at the maximum depth with
+    /// their onchain claim data, in a single batch.
+    async fn refresh_countered<M: Middleware + 'static>(
+        batch: &BatchCaller<M>,
+        game: &mut AlphabetGame,
+        layout: ClaimDataLayout,
+    ) -> Result<()> {
+        let indices = Self::steppable_claims(game).collect::<Vec<_>>();
+        if indices.is_empty() {
+            return Ok(());
+        }
+
+        let claims =
+            Self::fetch_claims(batch, game.address, layout, indices.iter().copied()).await?;
+        for (index, claim_data) in indices.into_iter().zip(claims) {
+            if claim_data.countered {
+                tracing::info!(target: "fault-game-watcher", "Claim at index={} in game at address {} was stepped against", index, game.address);
+            }
+            game.state[index].countered = claim_data.countered;
+        }
+        Ok(())
+    }
+
+    /// Fetches the length of the claim data array of each of the given games, and the
+    /// [ClaimDataLayout] of its elements, in a single batch. Games that predate the `version()`
+    /// getter use the [ClaimDataLayout::V0] layout, and games that predate the `claimDataLen()`
+    /// getter have the length read from storage. Games whose metadata can not be read are
+    /// logged and left out.
+    async fn fetch_metadata(
+        batch: &BatchCaller,
+        addresses: &[Address],
+    ) -> Result<HashMap<Address, (usize, ClaimDataLayout)>> {
+        let calls = addresses
+            .iter()
+            .flat_map(|&address| {
+                [
+                    Call::new(address, fault_dispute_game::ClaimDataLenCall),
+                    Call::new(address, fault_dispute_game::VersionCall),
+                ]
+            })
+            .collect();
+        let mut results = batch.call(calls).await?.into_iter();
+
+        let mut metadata = HashMap::with_capacity(addresses.len());
+        for &address in addresses {
+            let (Some(length), Some(version)) = (results.next(), results.next()) else {
+                anyhow::bail!("Missing results in batch of game metadata");
+            };
+            let layout = match version {
+                Ok(version) => {
+                    match multicall::decode::<fault_dispute_game::VersionReturn>(Ok(version))
+                        .and_then(|version| ClaimDataLayout::from_version(&version.0))
+                    {
+                        Ok(layout) => layout,
+                        Err(e) => {
+                            tracing::error!(target: "fault-game-watcher", "Failed to read the version of game at address {}: {}", address, e);
+                            continue;
+                        }
+                    }
+                }
+                Err(_) => ClaimDataLayout::V0,
+            };
+            let length = match multicall::decode::<fault_dispute_game::ClaimDataLenReturn>(length) {
+                Ok(length) => length.len,
+                Err(_) if layout == ClaimDataLayout::V0 => match batch
+                    .storage_at(address, H256::from_low_u64_be(V0_CLAIM_DATA_LEN_SLOT))
+                    .await
+                {
+                    Ok(length) => U256::from_big_endian(length.as_bytes()),
+                    Err(e) => {
+                        tracing::error!(target: "fault-game-watcher", "Failed to read the claim data length of game at address {} from storage: {}", address, e);
+                        continue;
+                    }
+                },
+                Err(e) => {
+                    tracing::error!(target: "fault-game-watcher", "Failed to read the claim data length of game at address {}: {}", address, e);
+                    continue;
+                }
+            };
+            metadata.insert(address, (length.as_usize(), layout));
+        }
+        Ok(metadata)
+    }
+
+    /// Fetches the [ClaimData] at the given indices of a game's claim data array in a single
+    /// batch, decoding it with the game's [ClaimDataLayout].
+    async fn fetch_claims<M: Middleware + 'static>(
+        batch: &BatchCaller<M>,
+        address: Address,
+        layout: ClaimDataLayout,
+        indices: impl IntoIterator<Item = usize>,
+    ) -> Result<Vec<ClaimData>> {
+        let calls = indices
+            .into_iter()
+            .map(|index| {
+                Call::new(
+                    address,
+                    fault_dispute_game::ClaimDataCall {
+                        index: index.into(),
+                    },
+                )
+            })
+            .collect();
+        batch
+            .call(calls)
+            .await?
+            .into_iter()
+            .map(|result| {
+                let (parent_index, countered, claim, position, clock) = match layout {
+                    ClaimDataLayout::V0 => {
+                        let claim_data =
+                            multicall::decode::<fault_dispute_game::ClaimDataReturn>(result)?;
+                        (
+                            claim_data.0,
+                            claim_data.1,
+                            claim_data.2,
+                            claim_data.3,
+                            claim_data.4,
+                        )
+                    }
+                    ClaimDataLayout::V1 => {
+                        let claim_data =
+                            multicall::decode::<fault_dispute_game_v1::ClaimDataReturn>(result)?;
+                        (
+                            claim_data.parent_index,
+                            !claim_data.countered_by.is_zero(),
+                            claim_data.claim,
+                            claim_data.position,
+                            claim_data.clock,
+                        )
+                    }
+                };
+                Ok(ClaimData {
+                    parent_index: parent_index as usize,
+                    countered,
+                    claim: claim.into(),
+                    position,
+                    clock: Clock {
+                        duration: (clock >> 64) as u64,
+                        timestamp: (clock & (u64::MAX as u128)) as u64,
+                    },
+                })
+            })
+            .collect()
+    }
+
+    /// Returns whether two [ClaimData] describe the same move. The `countered` flag of a claim
+    /// changes over the course of the game, so it is not compared.
+    fn is_same_claim(a: &ClaimData, b: &ClaimData) -> bool {
+        a.parent_index == b.parent_index
+            && a.claim == b.claim
+            && a.position == b.position
+            && a.clock == b.clock
+    }
+}
+
+define_driver!(
+    GameResolutionDriver,
+    (|self: GameResolutionDriver| {
+        async move {
+            loop {
+                tracing::info!(target: "game-resolution-driver", "Checking for resolvable FaultDisputeGames...");
+
+                // The chess clocks of a game are measured against the timestamp of the latest L1
+                // block, the same as in the contract.
+                let now = self
+                    .config
+                    .l1_provider
+                    .get_block(BlockNumber::Latest)
+                    .await?
+                    .ok_or(anyhow!("Latest L1 block not found"))?
+                    .timestamp
+                    .as_u64();
+
+                // Each game is locked in turn, without holding the lock on the global state. Games
+                // whose resolution was already journaled in dry-run mode are skipped.
+                let (tracked, simulated) = {
+                    let state = self.state.lock().await;
+                    (state.games(), state.simulated_resolutions.clone())
+                };
+                for (address, shared) in tracked {
+                    if simulated.contains(&address) {
+                        continue;
+                    }
+                    let mut game = shared.lock().await;
+                    let Some(resolution) = Self::resolution_due(&game, now) else {
+                        continue;
+                    };
+
+                    // Another participant may have resolved the game already.
                     let contract =
                         FaultDisputeGame::new(game.address, Arc::clone(&self.config.l1_provider));
+                    let onchain_status = match contract
+                        .status()
+                        .await
+                        .map_err(anyhow::Error::from)
+                        .and_then(GameStatus::try_from)
+                    {
+                        Ok(status) => status,
+                        Err(e) => {
+                            tracing::error!(target: "game-resolution-driver", "Failed to fetch the status of game at address {}: {}", game.address, e);
+                            continue;
+                        }
+                    };
+                    if onchain_status != GameStatus::InProgress {
+                        tracing::info!(target: "game-resolution-driver", "Game at address {} was already resolved as {:?}", game.address, onchain_status);
+                        game.status = onchain_status;
+                        self.save_game(&game).await;
+                        continue;
+                    }
 
-                    // TODO: Resolve when clocks are out.
-
-                    // Fetch the latest length of the claim data array in the game.
-                    // TODO: Just add a getter, it's a hassle to use `eth_getStorageAt` for this.
-                    // 🤮
-                    let mut slot = [0u8; 32];
-                    slot[31] = 0x01;
-                    let length = U256::from(
-                        self.config
-                            .l1_provider
-                            .get_storage_at(game.address, H256::from_slice(&slot), None)
-                            .await?
-                            .to_fixed_bytes(),
-                    )
-                    .as_usize();
-
-                    let local_len = game.state.len();
-                    match length.cmp(&local_len) {
-                        Ordering::Greater => {
-                            tracing::info!(target: "fault-game-watcher", "New claim data found in game at address {}. Fetching...", game.address);
-
-                            // Add the new claims to the local state and process them in-order.
-                            // TODO: Batch query here would reduce RPC calls by a lot.
-                            for i in local_len..length {
-                                // Fetch the claim data at the given index.
-                                let claim_data = contract.claim_data(i.into()).await?;
-
-                                // Add the new claim data to the local state.
-                                game.state.push(ClaimData {
-                                    parent_index: claim_data.0 as usize,
-                                    countered: claim_data.1,
-                                    claim: claim_data.2.into(),
-                                    position: claim_data.3,
-                                    clock: Clock {
-                                        duration: (claim_data.4 >> 64) as u64,
-                                        timestamp: (claim_data.4 & (u64::MAX as u128)) as u64,
-                                    },
-                                });
-
-                                // TODO(perf): We can be smarter about which claims we respond to. Fetch
-                                // the full state and only respond to claims that need a counter
-                                // from us. Maybe a `respond_to_all` function within the `FaultGame` trait
-                                // would be useful to hide this logic from the driver.
-                                match game.respond(i) {
-                                    Ok(res) => match res {
-                                        Response::Move(is_attack, claim, _) => {
-                                            tracing::debug!(target: "fault-game-watcher", "Dispatching move against claim at index={} for game at address {}", i, game.address);
-                                            // TODO: This is ugly. We should have a single function to
-                                            // dispatch a move.
-                                            let tx = if is_attack {
-                                                contract.attack(i.into(), claim.into()).tx
-                                            } else {
-                                                contract.defend(i.into(), claim.into()).tx
-                                            };
-                                            self.config.tx_sender.send(tx).await?;
-                                            tracing::info!(target: "fault-game-watcher", "Dispatched move against claim at index={} for game at address {}", i, game.address);
-
-                                            // We never need to respond to a secondary move because the
-                                            // claims are processed in-order.
-                                        }
-                                        Response::Step(
-                                            state_index,
-                                            parent_index,
-                                            is_attack,
-                                            state_data,
-                                            proof,
-                                        ) => {
-                                            let tx = contract
-                                                .step(
-                                                    state_index.into(),
-                                                    parent_index.into(),
-                                                    is_attack,
-                                                    state_data,
-                                                    proof,
-                                                )
-                                                .tx;
-                                            self.config.tx_sender.send(tx).await?;
-                                        }
-                                        _ => {
-                                            tracing::debug!(target: "fault-game-watcher", "No response to new claim (index: {}) at address {}", i, game.address);
-                                        }
-                                    },
-                                    Err(e) => {
-                                        tracing::error!(target: "fault-game-watcher", "Failed to formulate response to new claim data: {}", e);
-                                    }
-                                }
+                    // Simulate the resolution to fetch the resulting status before dispatching
+                    // the transaction.
+                    let call = contract.resolve();
+                    match call.call().await {
+                        Ok(status) => {
+                            let status = GameStatus::try_from(status)?;
+                            if status != resolution.status {
+                                tracing::warn!(target: "game-resolution-driver", "Game at address {} resolves as {:?}, but the local state predicts {:?}", game.address, status, resolution.status);
                             }
+                            let outcome = self
+                                .config
+                                .dispatch(call.tx, TxMeta::new(TxKind::Resolve).game(game.address))
+                                .await;
+                            // In dry-run mode the resolution is only simulated, so the game
+                            // stays in progress.
+                            if self.config.journal.is_none() {
+                                game.status = status;
+                            }
+                            tracing::info!(target: "game-resolution-driver", "Dispatched resolution of game at address {} as {:?}", game.address, status);
+                            tokio::spawn(Self::track_resolution(
+                                Arc::clone(&self.config),
+                                Arc::clone(&self.state),
+                                Arc::clone(&shared),
+                                status,
+                                outcome,
+                            ));
                         }
-                        Ordering::Less => {
-                            tracing::error!(target: "fault-game-watcher", "Local claim data length is greater than the on-chain length. This should never happen, please report this as a bug!! Local: {}, On-chain: {}", local_len, length);
-                        }
-                        _ => {
-                            tracing::debug!(target: "fault-game-watcher", "No new claim data found in game at address {}", game.address);
+                        Err(e) => {
+                            tracing::error!(target: "game-resolution-driver", "Failed to simulate the resolution of game at address {}: {}", game.address, e);
+                            continue;
                         }
                     }
+
+                    // Persist the updated game status.
+                    self.save_game(&game).await;
                 }
 
-                // Drop the mutex lock on the global state so that other drivers may access it
-                // while this thread sleeps.
-                drop(global_state);
+                // Check again in 1 minute.
+                tracing::debug!(target: "game-resolution-driver", "Done checking for resolvable games. Sleeping for 1 minute...");
+                tokio::time::sleep(Duration::from_secs(60)).await;
+            }
+        }
+    })
+);
 
-                // Check again in 5 minutes.
-                tracing::debug!(target: "fault-game-watcher", "Done checking for updates. Sleeping for 5 minutes...");
-                tokio::time::sleep(Duration::from_secs(60 * 5)).await;
+impl GameResolutionDriver {
+    /// Returns the [Resolution] of the given game, if the game is still in progress and can be
+    /// resolved at the given UNIX timestamp.
+    fn resolution_due(game: &AlphabetGame, now: u64) -> Option<Resolution> {
+        if game.status != GameStatus::InProgress {
+            return None;
+        }
+
+        let resolution = match game.resolution() {
+            Ok(resolution) => resolution,
+            Err(e) => {
+                tracing::error!(target: "game-resolution-driver", "Failed to compute the resolution of game at address {}: {}", game.address, e);
+                return None;
+            }
+        };
+        if !resolution.is_resolvable(now) {
+            tracing::debug!(target: "game-resolution-driver", "Game at address {} is not resolvable until timestamp {}", game.address, resolution.resolvable_at);
+            return None;
+        }
+        Some(resolution)
+    }
+
+    /// Persists the given game, logging any failure.
+    async fn save_game(&self, game: &AlphabetGame) {
+        Self::persist(&self.config, &self.state, game).await
+    }
+
+    /// Persists the given game to the store of the given [DriverConfig], logging any failure.
+    async fn persist(config: &DriverConfig, state: &Mutex<GlobalState>, game: &AlphabetGame) {
+        if let Err(e) = state.lock().await.save_game(config.store.as_ref(), game) {
+            tracing::error!(target: "game-resolution-driver", "Failed to persist game at address {}: {}", game.address, e);
+        }
+    }
+
+    /// Waits for the [TxOutcome] of the resolution of the given game. If the resolution was not
+    /// included, the game is marked as in progress again, so that its resolution is retried on
+    /// the next check. Simulated resolutions leave the status of the game untouched, but are not
+    /// simulated again.
+    async fn track_resolution(
+        config: Arc<DriverConfig>,
+        state: Arc<Mutex<GlobalState>>,
+        game: SharedGame,
+        status: GameStatus,
+        outcome: oneshot::Receiver<TxOutcome>,
+    ) {
+        let outcome = outcome.await;
+        let mut game = game.lock().await;
+        match outcome {
+            Ok(outcome @ TxOutcome::Simulated(_)) => {
+                tracing::info!(target: "game-resolution-driver", "Resolution of game at address {} {}. It would have resolved as {:?}.", game.address, outcome, status);
+                state
+                    .lock()
+                    .await
+                    .simulated_resolutions
+                    .insert(game.address);
+            }
+            Ok(outcome) if outcome.is_final() => {
+                tracing::info!(target: "game-resolution-driver", "Resolution of game at address {} {}", game.address, outcome);
             }
+            Ok(outcome) => {
+                // The onchain status of the game is checked again before the resolution is
+                // retried, so every revert is retried on the next check unless it can never
+                // succeed.
+                if outcome.revert().map(TxRevert::action) == Some(RevertAction::Alert) {
+                    tracing::error!(target: "game-resolution-driver", "Resolution of game at address {} {}. Retrying on the next check.", game.address, outcome);
+                } else {
+                    tracing::warn!(target: "game-resolution-driver", "Resolution of game at address {} {}. Retrying on the next check.", game.address, outcome);
+                }
+                game.status = GameStatus::InProgress;
+                Self::persist(&config, &state, &game).await;
+            }
+            Err(_) => {
+                tracing::warn!(target: "game-resolution-driver", "Outcome of resolution of game at address {} is unknown. Retrying on the next check.", game.address);
+                game.status = GameStatus::InProgress;
+                Self::persist(&config, &state, &game).await;
+            }
+        }
+    }
+}
+
+define_driver!(
+    OutputProposalDriver,
+    (|self: OutputProposalDriver| {
+        async move {
+            tracing::info!(target: "output-attestation-driver", "Subscribing to OutputProposed events...");
+
+            let factory = DisputeGame_Factory::new(
+                self.config.dispute_game_factory,
+                Arc::clone(&self.config.l1_provider),
+            );
+            let oracle = L2OutputOracle::new(
+                self.config.l2_output_oracle,
+                Arc::clone(&self.config.l1_provider),
+            );
+            let filter = oracle.output_proposed_filter().filter;
+
+            let (this, factory) = (&self, &factory);
+            utils::index_events(
+                &self.config,
+                &self.state,
+                "OutputProposed",
+                &filter,
+                |state| &mut state.last_output_block,
+                |log| async move {
+                    this.handle_output_proposed(factory, log).await;
+                    Ok(())
+                },
+            )
+            .await
         }
     })
 );
+
+impl OutputProposalDriver {
+    /// Runs the `OutputProposed` handler for the given event. Removed events are ignored, and
+    /// failures of the handler are logged without stopping the driver.
+    async fn handle_output_proposed(
+        &self,
+        factory: &DisputeGame_Factory<SignerMiddlewareWS>,
+        output_proposed: Log,
+    ) {
+        if output_proposed.removed == Some(true) {
+            tracing::debug!(target: "output-attestation-driver", "OutputProposed event was reorged out. Ignoring it.");
+            return;
+        }
+        if let Err(e) =
+            handlers::output_proposed(Arc::clone(&self.config), factory, output_proposed).await
+        {
+            // Soft failure, log the error and continue.
+            tracing::error!(target: "output-attestation-driver", "Error handling OutputProposed event: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DisputeFactoryDriver, FaultGameWatcherDriver, GameResolutionDriver, TRACE};
+    use crate::{
+        bindings::fault_dispute_game, multicall::BatchCaller, tx_manager::MAX_TX_ATTEMPTS,
+        ClaimDataLayout, GlobalState, TxOutcome, TxRevert,
+    };
+    use ethers::{
+        abi::AbiEncode,
+        providers::Provider,
+        types::{Address, BlockId, Bytes, TransactionReceipt, U256},
+    };
+    use op_challenger_solvers::fault::{
+        providers::AlphabetTraceProvider, AlphabetGame, Claim, ClaimData, Clock, FaultGame,
+        GameError, GameStatus, MAX_SUPPORTED_DEPTH,
+    };
+    use std::{collections::HashSet, sync::Arc};
+    use tokio::sync::{oneshot, Mutex};
+
+    /// The maximum depth of the games under test.
+    const MAX_DEPTH: u64 = 4;
+
+    /// The duration of the chess clocks of the games under test, in seconds.
+    const GAME_DURATION: u64 = 100;
+
+    /// Creates a new [AlphabetGame], created at timestamp 0, with a root claim and the given
+    /// moves, each made at the given timestamp against the previous claim.
+    async fn game(moves: &[(u128, u64)]) -> AlphabetGame {
+        let mut game = AlphabetGame::new(
+            Address::repeat_byte(1),
+            0,
+            MAX_DEPTH,
+            Claim::from_low_u64_be(15),
+            GAME_DURATION,
+            AlphabetTraceProvider::new(Arc::new(TRACE), MAX_DEPTH).unwrap(),
+        )
+        .await
+        .unwrap();
+        game.state.push(ClaimData {
+            parent_index: u32::MAX as usize,
+            countered: false,
+            claim: Claim::repeat_byte(1),
+            position: 1,
+            clock: Clock {
+                duration: 0,
+                timestamp: 0,
+            },
+        });
+        for (i, &(position, timestamp)) in moves.iter().enumerate() {
+            let parent = &game.state[i];
+            let duration = timestamp - parent.clock.timestamp;
+            game.state.push(ClaimData {
+                parent_index: i,
+                countered: false,
+                claim: Claim::repeat_byte(2),
+                position,
+                clock: Clock {
+                    duration,
+                    timestamp,
+                },
+            });
+        }
+        game
+    }
+
+    #[tokio::test]
+    async fn resolves_games_once_their_clocks_expire() {
+        // The root claim can no longer be countered once half of the game duration has passed.
+        let uncountered = game(&[]).await;
+        assert!(GameResolutionDriver::resolution_due(&uncountered, 50).is_none());
+        let resolution = GameResolutionDriver::resolution_due(&uncountered, 51).unwrap();
+        assert_eq!(resolution.status, GameStatus::DefenderWins);
+
+        // A counter to the root claim extends the game until its own clock runs out.
+        let countered = game(&[(2, 20)]).await;
+        assert!(GameResolutionDriver::resolution_due(&countered, 70).is_none());
+        let resolution = GameResolutionDriver::resolution_due(&countered, 71).unwrap();
+        assert_eq!(resolution.status, GameStatus::ChallengerWins);
+    }
+
+    #[tokio::test]
+    async fn skips_resolved_and_malformed_games() {
+        let mut resolved = game(&[]).await;
+        resolved.status = GameStatus::DefenderWins;
+        assert!(GameResolutionDriver::resolution_due(&resolved, u64::MAX).is_none());
+
+        // Games without claim data can not be resolved.
+        let mut empty = game(&[]).await;
+        empty.state.clear();
+        assert!(GameResolutionDriver::resolution_due(&empty, u64::MAX).is_none());
+    }
+
+    #[test]
+    fn checks_new_confirmed_blocks() {
+        // No blocks are checked for moves until every game has been synced once.
+        assert_eq!(FaultGameWatcherDriver::unsynced_blocks(None, 100), None);
+        assert_eq!(
+            FaultGameWatcherDriver::unsynced_blocks(Some(90), 100),
+            Some(91..=100)
+        );
+        assert_eq!(
+            FaultGameWatcherDriver::unsynced_blocks(Some(99), 100),
+            Some(100..=100)
+        );
+        // The confirmed block may not have moved, or may lag behind the last sync after a reorg.
+        assert_eq!(
+            FaultGameWatcherDriver::unsynced_blocks(Some(100), 100),
+            None
+        );
+        assert_eq!(
+            FaultGameWatcherDriver::unsynced_blocks(Some(101), 100),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn selects_games_to_sync() {
+        let address = Address::repeat_byte(1);
+        let synced = game(&[]).await;
+        let stale = HashSet::from([address]);
+
+        // Synced games are only synced if moves were made in them, unless they are stale.
+        assert_eq!(
+            FaultGameWatcherDriver::sync_required(address, &synced, &HashSet::new()),
+            Some(false)
+        );
+        assert_eq!(
+            FaultGameWatcherDriver::sync_required(address, &synced, &stale),
+            Some(true)
+        );
+
+        // New games are always synced.
+        let mut unsynced = game(&[]).await;
+        unsynced.state.clear();
+        assert_eq!(
+            FaultGameWatcherDriver::sync_required(address, &unsynced, &HashSet::new()),
+            Some(true)
+        );
+
+        // Resolved games are never synced.
+        let mut resolved = game(&[]).await;
+        resolved.status = GameStatus::ChallengerWins;
+        assert_eq!(
+            FaultGameWatcherDriver::sync_required(address, &resolved, &stale),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn observes_steps() {
+        let address = Address::repeat_byte(1);
+        let mut game = game(&[(2, 10), (4, 20), (8, 30), (16, 40)]).await;
+
+        // The uncountered claim at the maximum depth wins its subgame, and may still be stepped
+        // against, so the game is synced on every new block.
+        assert_eq!(game.resolution().unwrap().status, GameStatus::DefenderWins);
+        assert_eq!(
+            FaultGameWatcherDriver::sync_required(address, &game, &HashSet::new()),
+            Some(true)
+        );
+
+        // The claim at the maximum depth was stepped against onchain.
+        let (provider, mock) = Provider::mocked();
+        mock.push::<Bytes, _>(Bytes::new()).unwrap();
+        let batch = BatchCaller::new(Arc::new(provider), BlockId::from(1))
+            .await
+            .unwrap();
+        let leaf = &game.state[4];
+        let clock = ((leaf.clock.duration as u128) << 64) | leaf.clock.timestamp as u128;
+        mock.push::<Bytes, _>(Bytes::from(
+            fault_dispute_game::ClaimDataReturn(
+                leaf.parent_index as u32,
+                true,
+                leaf.claim.into(),
+                leaf.position,
+                clock,
+            )
+            .encode(),
+        ))
+        .unwrap();
+
+        FaultGameWatcherDriver::refresh_countered(&batch, &mut game, ClaimDataLayout::V0)
+            .await
+            .unwrap();
+        assert!(game.state[4].countered);
+        assert_eq!(
+            game.resolution().unwrap().status,
+            GameStatus::ChallengerWins
+        );
+        assert_eq!(
+            FaultGameWatcherDriver::sync_required(address, &game, &HashSet::new()),
+            Some(false)
+        );
+    }
+
+    /// Tracks a response to a claim, where each attempt has the next of the given outcomes.
+    /// Returns the number of times that the response was planned, including the first time.
+    async fn plan_count(state: &Mutex<GlobalState>, outcomes: Vec<Option<TxOutcome>>) -> usize {
+        let outcome = |outcome: Option<TxOutcome>| {
+            let (sender, receiver) = oneshot::channel();
+            // An outcome of `None` is unknown, as if the dispatcher dropped the request.
+            if let Some(outcome) = outcome {
+                sender.send(outcome).unwrap();
+            }
+            receiver
+        };
+        let mut outcomes = outcomes.into_iter();
+        let first = outcome(outcomes.next().unwrap());
+        let mut plans = 1;
+        FaultGameWatcherDriver::retry_response(state, Address::repeat_byte(1), 1, first, || {
+            plans += 1;
+            let next = outcomes.next().map(outcome);
+            async move { next }
+        })
+        .await;
+        plans
+    }
+
+    #[tokio::test]
+    async fn retries_responses() {
+        let state = Mutex::new(GlobalState::default());
+        let included = || Some(TxOutcome::Included(TransactionReceipt::default()));
+        let dropped = || Some(TxOutcome::Dropped("nonce too low".to_string()));
+        let reverted = |revert| Some(TxOutcome::Reverted(TransactionReceipt::default(), revert));
+
+        assert_eq!(plan_count(&state, vec![included()]).await, 1);
+        assert_eq!(plan_count(&state, vec![dropped(), included()]).await, 2);
+        assert_eq!(plan_count(&state, vec![None, included()]).await, 2);
+
+        // Reverts that can not be decoded are retried, up to the maximum number of attempts.
+        assert_eq!(
+            plan_count(
+                &state,
+                vec![dropped(), reverted(None), dropped(), included()]
+            )
+            .await,
+            MAX_TX_ATTEMPTS
+        );
+
+        // The response is not dispatched again once it is no longer needed, when re-planned or
+        // as the reason of its revert.
+        assert_eq!(plan_count(&state, vec![dropped()]).await, 2);
+        assert_eq!(
+            plan_count(
+                &state,
+                vec![reverted(Some(TxRevert::Game(GameError::GameNotInProgress)))]
+            )
+            .await,
+            1
+        );
+        assert!(state.lock().await.stale_games.is_empty());
+
+        // Nor if the game must be re-synced first.
+        assert_eq!(
+            plan_count(
+                &state,
+                vec![Some(TxOutcome::Rejected(TxRevert::Game(
+                    GameError::ClaimAlreadyExists
+                )))]
+            )
+            .await,
+            1
+        );
+        assert!(state
+            .lock()
+            .await
+            .stale_games
+            .contains(&Address::repeat_byte(1)));
+    }
+
+    #[test]
+    fn refuses_unsupported_depths() {
+        let provider =
+            DisputeFactoryDriver::alphabet_trace_provider(U256::from(MAX_SUPPORTED_DEPTH)).unwrap();
+        assert_eq!(provider.max_depth, MAX_SUPPORTED_DEPTH);
+        assert!(
+            DisputeFactoryDriver::alphabet_trace_provider(U256::from(MAX_SUPPORTED_DEPTH + 1))
+                .is_err()
+        );
+        assert!(DisputeFactoryDriver::alphabet_trace_provider(U256::MAX).is_err());
+        // The trace does not fit in a game that is too shallow.
+        assert!(DisputeFactoryDriver::alphabet_trace_provider(U256::from(3)).is_err());
+    }
+}
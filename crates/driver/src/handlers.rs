@@ -1,39 +1,56 @@
-use crate::{bindings::DisputeGame_Factory, utils, DriverConfig, GameType, SignerMiddlewareWS};
+use crate::{
+    bindings::{dispute_game_factory::CreateCall, DisputeGame_Factory, OutputProposedFilter},
+    tx_manager::MAX_TX_ATTEMPTS,
+    utils, DriverConfig, GameType, RevertAction, SignerMiddlewareWS, TxKind, TxMeta, TxOutcome,
+    TxRevert,
+};
 use anyhow::Result;
 use ethers::{
-    abi::Token,
+    abi::{AbiDecode, Token},
+    contract::parse_log,
     providers::Middleware,
-    types::{Log, Transaction, H256, U256},
+    types::{transaction::eip2718::TypedTransaction, Address, Log, Transaction, H256, U256},
 };
+use op_challenger_solvers::fault::Claim;
 use std::sync::Arc;
+use tokio::sync::oneshot;
 
 /// Handles the `OutputProposed` event emitted by the [L2OutputOracle]. This event is emitted when
 /// the [L2OutputOracle] receives a new output root from a proposer. This function will compare the
 /// output root proposed to L1 to the output root given to us by our trusted RPC. If the output roots
-/// do not match, the function will create a new output attestation game if there is not already a
-/// creation tx in the mempool.
-#[allow(dead_code)]
-pub async fn output_proposed(
+/// do not match, the function will create a new game of the configured [GameType], with the root
+/// claim that we believe in, if there is not already a creation tx in the mempool.
+pub(crate) async fn output_proposed(
     config: Arc<DriverConfig>,
     factory: &DisputeGame_Factory<SignerMiddlewareWS>,
     output_proposed: Log,
 ) -> Result<()> {
-    let proposed_root = output_proposed.topics.get(1).ok_or(anyhow::anyhow!(
-        "Critical failure: Output Root topic not present in `OutputProposed` event."
-    ))?;
-    // Convert the H256 representing the l2 block number into a u64.
-    let proposed_block = &output_proposed
-        .topics
-        .get(3)
-        .ok_or(anyhow::anyhow!(
-            "Critical failure: L2 Block Number topic not present in `OutputProposed` event."
-        ))?
-        .to_low_u64_be();
+    let event = parse_log::<OutputProposedFilter>(output_proposed.clone())?;
+
+    // Wait for the event to be confirmed before acting on it.
+    if !utils::wait_for_confirmation(
+        &config.l1_provider,
+        config.confirmations.output_proposals,
+        &output_proposed,
+    )
+    .await?
+    {
+        tracing::warn!(target: "output-attestation-driver", "OutputProposed event was reorged out before it was confirmed. Ignoring it.");
+        return Ok(());
+    }
+
+    let proposed_root = H256(event.output_root);
+    let proposed_block = u64::try_from(event.l_2_block_number).map_err(|_| {
+        anyhow::anyhow!(
+            "Critical failure: L2 block number {} in `OutputProposed` event does not fit in a u64.",
+            event.l_2_block_number
+        )
+    })?;
 
     match utils::compare_output_root(
         Arc::clone(&config.node_provider),
-        proposed_root,
-        *proposed_block,
+        &proposed_root,
+        proposed_block,
     )
     .await
     {
@@ -45,50 +62,48 @@ pub async fn output_proposed(
             } else {
                 tracing::warn!(target: "output-attestation-driver", "Output proposed by L1 does not match output at block on trusted node. L1: {:?}, L2: {:?}", proposed_root, output_at_block.output_root);
 
+                // Compute the root claim that we believe in for the configured game type.
+                let game_type = config.game_type;
+                let root_claim = match root_claim(game_type, output_at_block.output_root) {
+                    Ok(root_claim) => root_claim,
+                    Err(e) => {
+                        tracing::error!(target: "output-attestation-driver", "Failed to compute the root claim of a {:?} game: {}", game_type, e);
+                        return Ok(());
+                    }
+                };
+
                 // Check to see if someone has already challenged this output proposal.
                 tracing::debug!(target: "output-attestation-driver", "Checking to see if a challenge has already been submitted to L1 for the disagreed upon output...");
+                let challenge = challenge_call(game_type, root_claim, proposed_block);
                 let tx_pool_content = config.l1_provider.txpool_content().await?;
-                let is_pending_challenge =
-                    // Only check pending transactions that are about to be included.
-                    tx_pool_content.pending.values().any(|txs| {
-                        // TODO: This is a bit messy - we may want to pull out this
-                        // logic into a generic mempool search function.
-                        txs.values().any(|tx| {
-                            let Transaction { to, input, .. } = tx;
-                            *to == Some(config.dispute_game_factory)
-                                && input.starts_with(&[0x31, 0x42, 0xe5, 0x5e])
-                                && U256::from(&input[4..36])
-                                == U256::from(GameType::OutputAttestation as u8)
-                                && H256::from_slice(&input[36..68]) == *proposed_root
-                        })
-                    });
+                // Only check pending transactions that are about to be included.
+                let is_pending_challenge = tx_pool_content
+                    .pending
+                    .values()
+                    .flat_map(|txs| txs.values())
+                    .any(|tx| is_pending_challenge(tx, config.dispute_game_factory, &challenge));
 
                 // If no one has challenged this output proposal, submit a challenge.
                 // If someone has already sent a transaction to challenge this output,
                 // we can safely ignore it as the `DisputeFactoryDriver` will pick up
                 // the challenge and submit a response.
                 if !is_pending_challenge {
-                    tracing::info!(target: "output-attestation-driver", "No pending challenge found, submitting challenge to L1.");
-
-                    // TODO: Consult cannon.
-                    let initial_claim = [0u8; 32];
-
-                    // Send a challenge creation transaction to the L1 dispute game factory.
-                    config
-                        .tx_sender
-                        .send(
-                            factory
-                                .create(
-                                    GameType::Fault as u8,
-                                    initial_claim,
-                                    ethers::abi::encode(&[Token::Uint(U256::from(
-                                        *proposed_block,
-                                    ))])
-                                    .into(),
-                                )
-                                .tx,
+                    tracing::info!(target: "output-attestation-driver", "No pending challenge found, submitting challenge to L1 as a {:?} game with root claim {:?}.", game_type, root_claim);
+
+                    // Send a challenge creation transaction to the L1 dispute game factory. Its
+                    // outcome is tracked in the background, so that other proposals are not
+                    // held up while it is retried until it is included.
+                    let tx = factory
+                        .create(
+                            challenge.game_type,
+                            challenge.root_claim,
+                            challenge.extra_data,
                         )
-                        .await?;
+                        .tx;
+                    let outcome = config
+                        .dispatch(tx.clone(), TxMeta::new(TxKind::CreateGame))
+                        .await;
+                    tokio::spawn(track_creation(Arc::clone(&config), tx, outcome));
                 } else {
                     tracing::debug!(target: "output-attestation-driver", "Pending challenge found, waiting for the game to be created.")
                 }
@@ -102,3 +117,195 @@ pub async fn output_proposed(
 
     Ok(())
 }
+
+/// Waits for the [TxOutcome] of a challenge creation, dispatching it again until it is included
+/// or [MAX_TX_ATTEMPTS] attempts have failed.
+///
+/// ### Takes
+/// - `config`: The driver configuration.
+/// - `tx`: The challenge creation transaction.
+/// - `outcome`: The receiving handle of the [TxOutcome] of the first attempt.
+async fn track_creation(
+    config: Arc<DriverConfig>,
+    tx: TypedTransaction,
+    mut outcome: oneshot::Receiver<TxOutcome>,
+) {
+    for attempt in 1..=MAX_TX_ATTEMPTS {
+        match outcome.await {
+            Ok(outcome) if outcome.is_final() => {
+                tracing::info!(target: "output-attestation-driver", "Challenge creation {}", outcome);
+                return;
+            }
+            Ok(outcome) => match outcome.revert().map(TxRevert::action) {
+                Some(RevertAction::Drop | RevertAction::Resync) => {
+                    tracing::warn!(target: "output-attestation-driver", "Challenge creation {}. Not retrying.", outcome);
+                    return;
+                }
+                Some(RevertAction::Alert) => {
+                    tracing::error!(target: "output-attestation-driver", "Challenge creation {}. The challenge can not be created; not retrying.", outcome);
+                    return;
+                }
+                None => {
+                    tracing::warn!(target: "output-attestation-driver", "Challenge creation {} (attempt {}/{})", outcome, attempt, MAX_TX_ATTEMPTS);
+                }
+            },
+            Err(_) => {
+                tracing::warn!(target: "output-attestation-driver", "Outcome of challenge creation is unknown (attempt {}/{})", attempt, MAX_TX_ATTEMPTS);
+            }
+        }
+        if attempt == MAX_TX_ATTEMPTS {
+            tracing::error!(target: "output-attestation-driver", "Giving up on creating the challenge.");
+            return;
+        }
+        outcome = config
+            .dispatch(tx.clone(), TxMeta::new(TxKind::CreateGame))
+            .await;
+    }
+}
+
+/// Returns the `create` call to the dispute game factory that challenges the output proposed
+/// for the given L2 block, with a game of the given type and root claim.
+fn challenge_call(game_type: GameType, root_claim: Claim, proposed_block: u64) -> CreateCall {
+    CreateCall {
+        game_type: game_type as u8,
+        root_claim: root_claim.into(),
+        extra_data: ethers::abi::encode(&[Token::Uint(U256::from(proposed_block))]).into(),
+    }
+}
+
+/// Returns whether the given transaction creates the same game as the given challenge.
+///
+/// ### Takes
+/// - `tx`: A pending transaction.
+/// - `factory`: The address of the dispute game factory.
+/// - `challenge`: The `create` call of the challenge.
+fn is_pending_challenge(tx: &Transaction, factory: Address, challenge: &CreateCall) -> bool {
+    // The root claim alone does not identify the game: the game type and the disputed L2 block
+    // are part of its UUID as well.
+    tx.to == Some(factory) && CreateCall::decode(&tx.input).is_ok_and(|call| call == *challenge)
+}
+
+/// Computes the root claim that we believe in for a game of the given type, disputing an output
+/// proposal that does not match the trusted output root.
+///
+/// ### Takes
+/// - `game_type`: The type of the game.
+/// - `trusted_output_root`: The output root at the disputed L2 block, according to the trusted
+///   op-node.
+///
+/// ### Returns
+/// - `Ok(Claim)`: The trusted output root for output attestation games.
+/// - `Err(anyhow::Error)`: The game type is not supported.
+fn root_claim(game_type: GameType, trusted_output_root: H256) -> Result<Claim> {
+    match game_type {
+        GameType::OutputAttestation => Ok(trusted_output_root),
+        // The root claim of a fault game must commit to the output trace up to the disputed L2
+        // block, which is not wired into game creation yet. The alphabet trace that the
+        // challenger plays with commits to the same state whatever the disputed block, so games
+        // created with it could never be won.
+        GameType::Fault => anyhow::bail!(
+            "Creating Fault games is not supported until their root claim commits to the output trace"
+        ),
+        GameType::Validity => anyhow::bail!("Validity games are not supported"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{challenge_call, is_pending_challenge, root_claim};
+    use crate::{
+        bindings::dispute_game_factory::{CreateCall, GameImplsCall},
+        GameType,
+    };
+    use ethers::{
+        abi::AbiEncode,
+        types::{Address, Bytes, Transaction, U256},
+    };
+    use op_challenger_solvers::fault::Claim;
+
+    /// The address of the dispute game factory.
+    const FACTORY: Address = Address::repeat_byte(0xfa);
+
+    /// Creates a pending transaction to the given contract with the given calldata.
+    fn tx(to: Address, input: impl Into<Bytes>) -> Transaction {
+        Transaction {
+            to: Some(to),
+            input: input.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn plans_challenges() {
+        let challenge = challenge_call(GameType::Fault, Claim::repeat_byte(1), 100);
+        assert_eq!(challenge.game_type, GameType::Fault as u8);
+        assert_eq!(challenge.root_claim, [1; 32]);
+        // The extra data of the game is the disputed L2 block number.
+        assert_eq!(challenge.extra_data, Bytes::from(U256::from(100).encode()));
+    }
+
+    #[test]
+    fn computes_root_claims() {
+        let trusted = Claim::repeat_byte(1);
+        assert_eq!(
+            root_claim(GameType::OutputAttestation, trusted).unwrap(),
+            trusted
+        );
+        // Fault games are not created until their root claim commits to the output trace.
+        assert!(root_claim(GameType::Fault, trusted).is_err());
+        assert!(root_claim(GameType::Validity, trusted).is_err());
+    }
+
+    #[test]
+    fn detects_pending_challenges() {
+        let challenge = challenge_call(GameType::Fault, Claim::repeat_byte(1), 100);
+        assert!(is_pending_challenge(
+            &tx(FACTORY, challenge.clone().encode()),
+            FACTORY,
+            &challenge
+        ));
+
+        // Transactions to other contracts, or other calls to the factory, are not challenges.
+        assert!(!is_pending_challenge(
+            &tx(Address::zero(), challenge.clone().encode()),
+            FACTORY,
+            &challenge
+        ));
+        assert!(!is_pending_challenge(
+            &tx(FACTORY, GameImplsCall { game_type: 0 }.encode()),
+            FACTORY,
+            &challenge
+        ));
+        assert!(!is_pending_challenge(
+            &tx(FACTORY, challenge.clone().encode()[..40].to_vec()),
+            FACTORY,
+            &challenge
+        ));
+
+        // Challenges with another game type or root claim create a different game.
+        let other_type = challenge_call(GameType::OutputAttestation, Claim::repeat_byte(1), 100);
+        assert!(!is_pending_challenge(
+            &tx(FACTORY, other_type.encode()),
+            FACTORY,
+            &challenge
+        ));
+        let other_root = CreateCall {
+            root_claim: [2; 32],
+            ..challenge.clone()
+        };
+        assert!(!is_pending_challenge(
+            &tx(FACTORY, other_root.encode()),
+            FACTORY,
+            &challenge
+        ));
+
+        // Challenges to other output proposals have the same root claim, but dispute another L2
+        // block.
+        let other_block = challenge_call(GameType::Fault, Claim::repeat_byte(1), 101);
+        assert!(!is_pending_challenge(
+            &tx(FACTORY, other_block.encode()),
+            FACTORY,
+            &challenge
+        ));
+    }
+}
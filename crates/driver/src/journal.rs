@@ -0,0 +1,144 @@
+//! The journal module contains the [TxJournal], which records the transactions that the
+//! [TxDispatchDriver](crate::TxDispatchDriver) would have sent in dry-run mode.
+
+use crate::{TxKind, TxRequest};
+use anyhow::Result;
+use ethers::types::{Address, Bytes, NameOrAddress, U256};
+use serde::Serialize;
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    path::Path,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// An entry in the [TxJournal].
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JournalEntry<'a> {
+    /// The UNIX timestamp at which the transaction was simulated.
+    timestamp: u64,
+    /// The purpose of the transaction.
+    kind: TxKind,
+    /// The address of the game that the transaction acts on, if any.
+    game: Option<Address>,
+    /// The UNIX timestamp after which the transaction is useless, if any.
+    deadline: Option<u64>,
+    /// The recipient of the transaction.
+    to: Option<&'a NameOrAddress>,
+    /// The calldata of the transaction.
+    data: Option<&'a Bytes>,
+    /// The value of the transaction.
+    value: Option<&'a U256>,
+    /// The return data of the simulation, if it succeeded.
+    return_data: Option<&'a Bytes>,
+    /// The revert reason of the simulation, if it failed.
+    revert_reason: Option<&'a str>,
+}
+
+/// The [TxJournal] appends every transaction that would have been sent, along with the result
+/// of its simulation, to a file of JSON lines.
+pub struct TxJournal {
+    /// The journal file.
+    file: Mutex<File>,
+}
+
+impl TxJournal {
+    /// Opens the journal at the given path, creating it if it does not exist. Entries are
+    /// appended to any existing journal.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self {
+            file: Mutex::new(OpenOptions::new().create(true).append(true).open(path)?),
+        })
+    }
+
+    /// Records a transaction that would have been sent.
+    ///
+    /// ### Takes
+    /// - `request`: The request to send the transaction.
+    /// - `simulation`: The return data of the simulated transaction, or its revert reason.
+    pub fn record(
+        &self,
+        request: &TxRequest,
+        simulation: &std::result::Result<Bytes, String>,
+    ) -> Result<()> {
+        let entry = JournalEntry {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            kind: request.meta.kind,
+            game: request.meta.game,
+            deadline: request.meta.deadline,
+            to: request.tx.to(),
+            data: request.tx.data(),
+            value: request.tx.value(),
+            return_data: simulation.as_ref().ok(),
+            revert_reason: simulation.as_ref().err().map(String::as_str),
+        };
+        let mut line = serde_json::to_vec(&entry)?;
+        line.push(b'\n');
+
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        file.write_all(&line)?;
+        file.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TxJournal;
+    use crate::{TxKind, TxMeta, TxRequest};
+    use ethers::types::{Address, Bytes, TransactionRequest};
+    use std::fs;
+
+    #[test]
+    fn records_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("journal.jsonl");
+        let journal = TxJournal::open(&path).unwrap();
+
+        let tx = TransactionRequest::new()
+            .to(Address::repeat_byte(1))
+            .data(Bytes::from_static(&[0x2b, 0x31, 0x84, 0x1e]));
+        let (request, _) = TxRequest::new(
+            tx.into(),
+            TxMeta::new(TxKind::Resolve)
+                .game(Address::repeat_byte(1))
+                .deadline(100),
+        );
+        journal
+            .record(&request, &Ok(Bytes::from_static(&[1])))
+            .unwrap();
+        journal
+            .record(&request, &Err("Game not in progress".to_string()))
+            .unwrap();
+
+        // Entries are appended to an existing journal when it is reopened.
+        drop(journal);
+        TxJournal::open(&path)
+            .unwrap()
+            .record(&request, &Ok(Bytes::new()))
+            .unwrap();
+
+        let entries = fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(entries.len(), 3);
+        let entry = &entries[0];
+        assert_eq!(entry["kind"], "Resolve");
+        assert_eq!(entry["game"], format!("{:?}", Address::repeat_byte(1)));
+        assert_eq!(entry["deadline"], 100);
+        assert_eq!(entry["to"], format!("{:?}", Address::repeat_byte(1)));
+        assert_eq!(entry["data"], "0x2b31841e");
+        assert_eq!(entry["returnData"], "0x01");
+        assert!(entry["revertReason"].is_null());
+        assert!(entries[1]["returnData"].is_null());
+        assert_eq!(entries[1]["revertReason"], "Game not in progress");
+        assert_eq!(entries[2]["returnData"], "0x");
+    }
+}
@@ -9,13 +9,26 @@ mod config;
 pub use config::DriverConfig;
 
 mod state;
-pub use state::GlobalState;
+pub use state::{GlobalState, SharedGame};
+
+mod store;
+pub use store::{MemoryStore, SledStore, StateStore};
 
 mod drivers;
 pub use drivers::*;
 
 mod handlers;
 
+mod journal;
+pub use journal::TxJournal;
+
+mod multicall;
+
+mod tx_manager;
+
+mod tx_queue;
+pub use tx_queue::{TxQueue, TxQueueMetrics};
+
 mod types;
 pub use types::*;
 
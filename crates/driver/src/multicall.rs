@@ -0,0 +1,271 @@
+//! The multicall module contains the [BatchCaller], which batches read-only contract calls into
+//! a single `aggregate3` call to the [Multicall3] contract.
+
+use crate::{
+    bindings::{Call3, Multicall3},
+    SignerMiddlewareWS,
+};
+use anyhow::{anyhow, Result};
+use ethers::{
+    abi::{AbiDecode, AbiEncode},
+    contract::MULTICALL_ADDRESS,
+    providers::{Middleware, MiddlewareError},
+    types::{
+        transaction::eip2718::TypedTransaction, Address, BlockId, Bytes, TransactionRequest, H256,
+    },
+};
+use futures::{stream, StreamExt, TryStreamExt};
+use std::sync::Arc;
+
+/// The maximum number of calls aggregated into a single `aggregate3` call.
+const MULTICALL_BATCH_SIZE: usize = 256;
+
+/// The maximum number of individual calls in flight when [Multicall3] is not deployed.
+const MAX_CONCURRENT_CALLS: usize = 16;
+
+/// A read-only call to a contract.
+#[derive(Debug, Clone)]
+pub(crate) struct Call {
+    /// The address of the contract.
+    pub target: Address,
+    /// The ABI encoded calldata.
+    pub data: Bytes,
+}
+
+impl Call {
+    /// Creates a new [Call] to the given contract with the given ABI encoded function call.
+    pub(crate) fn new(target: Address, call: impl AbiEncode) -> Self {
+        Self {
+            target,
+            data: call.encode().into(),
+        }
+    }
+}
+
+/// The result of a [Call]: the return data if the call succeeded, or the revert data if it
+/// reverted.
+pub(crate) type CallResult = std::result::Result<Bytes, Bytes>;
+
+/// Decodes the return data of a [Call], failing if the call reverted.
+pub(crate) fn decode<T: AbiDecode>(result: CallResult) -> Result<T> {
+    let data = result.map_err(|revert| anyhow!("Call reverted with data {}", revert))?;
+    Ok(T::decode(data)?)
+}
+
+/// The [BatchCaller] executes batches of [Call]s at a fixed L1 block. If the [Multicall3] contract
+/// is deployed, each batch is sent as a single `aggregate3` call. Otherwise, the calls are sent
+/// individually, with a bounded number of calls in flight.
+pub(crate) struct BatchCaller<M = SignerMiddlewareWS> {
+    /// The L1 provider.
+    client: Arc<M>,
+    /// The L1 block that the calls are executed at.
+    block: BlockId,
+    /// The [Multicall3] contract, if it is deployed.
+    multicall: Option<Multicall3<M>>,
+}
+
+impl<M: Middleware + 'static> BatchCaller<M> {
+    /// Creates a new [BatchCaller] that executes calls at the given L1 block, checking whether
+    /// the [Multicall3] contract is deployed at that block.
+    pub(crate) async fn new(client: Arc<M>, block: BlockId) -> Result<Self> {
+        let code = client.get_code(MULTICALL_ADDRESS, Some(block)).await?;
+        let multicall = if code.is_empty() {
+            tracing::debug!(target: "multicall", "Multicall3 is not deployed at {:?}. Falling back to individual calls.", MULTICALL_ADDRESS);
+            None
+        } else {
+            Some(Multicall3::new(MULTICALL_ADDRESS, Arc::clone(&client)))
+        };
+        Ok(Self {
+            client,
+            block,
+            multicall,
+        })
+    }
+
+    /// Executes the given calls, returning their results in the same order.
+    ///
+    /// ### Takes
+    /// - `calls`: The calls to execute.
+    ///
+    /// ### Returns
+    /// - `Ok(Vec<CallResult>)`: The result of each call. A reverted call does not fail the batch.
+    /// - `Err(anyhow::Error)`: A request to the L1 provider failed.
+    pub(crate) async fn call(&self, calls: Vec<Call>) -> Result<Vec<CallResult>> {
+        match &self.multicall {
+            Some(multicall) => {
+                let mut results = Vec::with_capacity(calls.len());
+                for chunk in calls.chunks(MULTICALL_BATCH_SIZE) {
+                    let chunk = chunk
+                        .iter()
+                        .map(|call| Call3 {
+                            target: call.target,
+                            allow_failure: true,
+                            call_data: call.data.clone(),
+                        })
+                        .collect();
+                    results.extend(
+                        multicall
+                            .aggregate_3(chunk)
+                            .block(self.block)
+                            .call()
+                            .await?
+                            .into_iter()
+                            .map(|(success, return_data)| {
+                                if success {
+                                    Ok(return_data)
+                                } else {
+                                    Err(return_data)
+                                }
+                            }),
+                    );
+                }
+                Ok(results)
+            }
+            None => {
+                stream::iter(calls)
+                    .map(|call| self.call_single(call))
+                    .buffered(MAX_CONCURRENT_CALLS)
+                    .try_collect()
+                    .await
+            }
+        }
+    }
+
+    /// Reads the given storage slot of a contract at the L1 block that the calls are executed at.
+    pub(crate) async fn storage_at(&self, address: Address, slot: H256) -> Result<H256> {
+        Ok(self
+            .client
+            .get_storage_at(address, slot, Some(self.block))
+            .await?)
+    }
+
+    /// Executes a single call with `eth_call`.
+    async fn call_single(&self, call: Call) -> Result<CallResult> {
+        let tx: TypedTransaction = TransactionRequest::new()
+            .to(call.target)
+            .data(call.data)
+            .into();
+        match self.client.call(&tx, Some(self.block)).await {
+            Ok(data) => Ok(Ok(data)),
+            Err(e) => match e.as_error_response().and_then(|e| e.as_revert_data()) {
+                Some(revert) => Ok(Err(revert)),
+                None => Err(e.into()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decode, BatchCaller, Call, MULTICALL_BATCH_SIZE};
+    use crate::bindings::fault_dispute_game::{ClaimDataLenCall, ClaimDataLenReturn};
+    use ethers::{
+        abi::AbiEncode,
+        providers::{JsonRpcError, MockProvider, MockResponse, Provider},
+        types::{Address, BlockId, Bytes, U256},
+    };
+    use std::sync::Arc;
+
+    /// Creates a [BatchCaller] backed by a [MockProvider], which reports whether Multicall3
+    /// is deployed.
+    async fn batch_caller(multicall: bool) -> (BatchCaller<Provider<MockProvider>>, MockProvider) {
+        let (provider, mock) = Provider::mocked();
+        let code = if multicall {
+            Bytes::from_static(&[0x60])
+        } else {
+            Bytes::new()
+        };
+        mock.push::<Bytes, _>(code).unwrap();
+        let batch = BatchCaller::new(Arc::new(provider), BlockId::from(1))
+            .await
+            .unwrap();
+        (batch, mock)
+    }
+
+    /// Returns the ABI encoded return data of a `claimDataLen()` call.
+    fn len(len: u64) -> Bytes {
+        ClaimDataLenReturn { len: len.into() }.encode().into()
+    }
+
+    /// Returns an `eth_call` error response that reverted with the given data.
+    fn revert(data: &Bytes) -> MockResponse {
+        MockResponse::Error(JsonRpcError {
+            code: 3,
+            message: "execution reverted".to_string(),
+            data: Some(serde_json::Value::String(data.to_string())),
+        })
+    }
+
+    #[tokio::test]
+    async fn aggregates_calls_in_chunks() {
+        let (batch, mock) = batch_caller(true).await;
+        let calls = (0..MULTICALL_BATCH_SIZE + 2)
+            .map(|i| Call::new(Address::from_low_u64_be(i as u64), ClaimDataLenCall))
+            .collect::<Vec<_>>();
+
+        // The responses are served from the back, so the last chunk is pushed first.
+        let results = |range: std::ops::Range<usize>| {
+            range
+                .map(|i| (i % 2 == 0, len(i as u64)))
+                .collect::<Vec<_>>()
+                .encode()
+        };
+        mock.push::<Bytes, _>(Bytes::from(results(MULTICALL_BATCH_SIZE..calls.len())))
+            .unwrap();
+        mock.push::<Bytes, _>(Bytes::from(results(0..MULTICALL_BATCH_SIZE)))
+            .unwrap();
+
+        let results = batch.call(calls).await.unwrap();
+        assert_eq!(results.len(), MULTICALL_BATCH_SIZE + 2);
+        for (i, result) in results.into_iter().enumerate() {
+            if i % 2 == 0 {
+                let decoded = decode::<ClaimDataLenReturn>(result).unwrap();
+                assert_eq!(decoded.len, U256::from(i));
+            } else {
+                // Failed calls hold their revert data, and fail to decode.
+                assert_eq!(result, Err(len(i as u64)));
+                assert!(decode::<ClaimDataLenReturn>(result).is_err());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_individual_calls() {
+        let (batch, mock) = batch_caller(false).await;
+        let reverted = Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]);
+        mock.push_response(revert(&reverted));
+        mock.push::<Bytes, _>(len(7)).unwrap();
+
+        let calls = vec![
+            Call::new(Address::repeat_byte(1), ClaimDataLenCall),
+            Call::new(Address::repeat_byte(2), ClaimDataLenCall),
+        ];
+        let results = batch.call(calls.clone()).await.unwrap();
+        assert_eq!(
+            decode::<ClaimDataLenReturn>(results[0].clone())
+                .unwrap()
+                .len,
+            U256::from(7)
+        );
+        assert_eq!(results[1], Err(reverted));
+
+        // Errors that are not reverts fail the whole batch.
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: -32000,
+            message: "header not found".to_string(),
+            data: None,
+        }));
+        mock.push::<Bytes, _>(len(7)).unwrap();
+        assert!(batch.call(calls).await.is_err());
+    }
+
+    #[test]
+    fn decodes_results() {
+        assert_eq!(
+            decode::<ClaimDataLenReturn>(Ok(len(3))).unwrap().len,
+            U256::from(3)
+        );
+        assert!(decode::<ClaimDataLenReturn>(Ok(Bytes::from_static(&[1, 2, 3]))).is_err());
+        assert!(decode::<ClaimDataLenReturn>(Err(len(3))).is_err());
+    }
+}
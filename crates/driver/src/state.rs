@@ -1,11 +1,172 @@
 //! The state module holds the [GlobalState] struct, which is shared between all drivers.
 
+use crate::{L1BlockRef, StateStore};
+use anyhow::Result;
+use ethers::types::Address;
 use op_challenger_solvers::fault::AlphabetGame;
-use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+use tokio::sync::Mutex;
+
+/// A tracked [AlphabetGame]. Each game has its own lock, so that games can be synced and
+/// responded to concurrently without holding the lock on the [GlobalState].
+pub type SharedGame = Arc<Mutex<AlphabetGame>>;
 
 /// The [GlobalState] struct holds all of the shared state between drivers.
-#[derive(Default, Serialize, Deserialize)]
+///
+/// The lock on the [GlobalState] must never be held while waiting on the lock of a
+/// [SharedGame]. Drivers clone the [SharedGame]s they need and release the [GlobalState] before
+/// locking them.
+#[derive(Default)]
 pub struct GlobalState {
-    /// A mutable vector of all [AlphabetGame]s that are currently in progress.
-    pub alphabet_games: Vec<AlphabetGame>,
+    /// The [AlphabetGame]s that are being tracked, keyed by their address.
+    pub alphabet_games: HashMap<Address, SharedGame>,
+    /// The number of the last L1 block that has been processed.
+    pub last_l1_block: Option<u64>,
+    /// The number of the last L1 block whose `OutputProposed` events have been processed.
+    pub last_output_block: Option<u64>,
+    /// The L1 blocks that the `DisputeGameCreated` events of the tracked games were emitted in,
+    /// keyed by the address of the game.
+    pub game_origins: HashMap<Address, L1BlockRef>,
+    /// The games whose local claim data is known to be out of date, because a response to them
+    /// reverted. They are re-synced by the [FaultGameWatcherDriver](crate::FaultGameWatcherDriver)
+    /// on the next L1 head. Not persisted.
+    pub stale_games: HashSet<Address>,
+    /// The games whose resolution has been simulated and journaled in dry-run mode. Their
+    /// resolution is not journaled again. Not persisted.
+    pub simulated_resolutions: HashSet<Address>,
+}
+
+impl GlobalState {
+    /// Returns the tracked games, so that they can be locked after the lock on the
+    /// [GlobalState] is released.
+    pub fn games(&self) -> Vec<(Address, SharedGame)> {
+        self.alphabet_games
+            .iter()
+            .map(|(address, game)| (*address, Arc::clone(game)))
+            .collect()
+    }
+
+    /// Stops tracking the game at the given address, because the event that created it was
+    /// reorged out. The cursor is rolled back to before the game's origin block, so that the
+    /// block range is synced again.
+    ///
+    /// ### Takes
+    /// - `address`: The address of the game.
+    ///
+    /// ### Returns
+    /// - `true` if the game was being tracked.
+    pub fn remove_game(&mut self, address: Address) -> bool {
+        if let Some(origin) = self.game_origins.remove(&address) {
+            self.last_l1_block = self
+                .last_l1_block
+                .min(Some(origin.number.saturating_sub(1)));
+        }
+        self.alphabet_games.remove(&address).is_some()
+    }
+
+    /// Persists the given game to the store, if it is still being tracked. A game may stop
+    /// being tracked while its own lock is held, and must not be persisted again once it has
+    /// been removed.
+    ///
+    /// ### Takes
+    /// - `store`: The storage backend.
+    /// - `game`: The game to persist.
+    pub fn save_game(&self, store: &dyn StateStore, game: &AlphabetGame) -> Result<()> {
+        if self.alphabet_games.contains_key(&game.address) {
+            store.save_game(game)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::GlobalState;
+    use crate::{L1BlockRef, SledStore, StateStore};
+    use ethers::types::{Address, H256};
+    use op_challenger_solvers::fault::{providers::AlphabetTraceProvider, AlphabetGame, Claim};
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    /// Creates a new [AlphabetGame] at the given address.
+    async fn game(address: Address) -> AlphabetGame {
+        AlphabetGame::new(
+            address,
+            0,
+            4,
+            Claim::from_low_u64_be(96),
+            100,
+            AlphabetTraceProvider::new(Arc::new(*b"abcdefghijklmnop"), 4).unwrap(),
+        )
+        .await
+        .unwrap()
+    }
+
+    /// Creates a [GlobalState] that tracks a game created in each of the given L1 blocks, with
+    /// the cursor at the given block.
+    async fn state(origins: &[u64], cursor: u64) -> GlobalState {
+        let mut state = GlobalState {
+            last_l1_block: Some(cursor),
+            ..Default::default()
+        };
+        for (i, number) in origins.iter().enumerate() {
+            let address = Address::repeat_byte(i as u8 + 1);
+            state
+                .alphabet_games
+                .insert(address, Arc::new(Mutex::new(game(address).await)));
+            state.game_origins.insert(
+                address,
+                L1BlockRef {
+                    number: *number,
+                    hash: H256::repeat_byte(*number as u8),
+                },
+            );
+        }
+        state
+    }
+
+    #[tokio::test]
+    async fn removing_games_rolls_back_cursor() {
+        let mut state = state(&[90, 95, 120], 100).await;
+
+        // The cursor is rolled back to before the origin of the removed game.
+        assert!(state.remove_game(Address::repeat_byte(2)));
+        assert_eq!(state.last_l1_block, Some(94));
+        assert!(!state.alphabet_games.contains_key(&Address::repeat_byte(2)));
+        assert!(!state.game_origins.contains_key(&Address::repeat_byte(2)));
+
+        // It is never moved forwards.
+        assert!(state.remove_game(Address::repeat_byte(3)));
+        assert_eq!(state.last_l1_block, Some(94));
+        assert!(state.remove_game(Address::repeat_byte(1)));
+        assert_eq!(state.last_l1_block, Some(89));
+
+        // Untracked games are ignored.
+        assert!(!state.remove_game(Address::repeat_byte(1)));
+        assert_eq!(state.last_l1_block, Some(89));
+        assert!(state.alphabet_games.is_empty());
+        assert!(state.game_origins.is_empty());
+    }
+
+    #[tokio::test]
+    async fn only_saves_tracked_games() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SledStore::open(dir.path()).unwrap();
+        let mut state = state(&[90, 95], 100).await;
+        state.alphabet_games.remove(&Address::repeat_byte(2));
+        store.save(&state).unwrap();
+
+        for address in [Address::repeat_byte(1), Address::repeat_byte(2)] {
+            state.save_game(&store, &game(address).await).unwrap();
+        }
+        let loaded = store.load().unwrap().unwrap();
+        assert_eq!(
+            loaded.alphabet_games.keys().collect::<Vec<_>>(),
+            vec![&Address::repeat_byte(1)]
+        );
+        assert_eq!(loaded.last_l1_block, Some(100));
+    }
 }
@@ -0,0 +1,276 @@
+//! The store module contains the [StateStore] trait and its implementations, which persist the
+//! [GlobalState] across restarts of the `op-challenger`.
+
+use crate::GlobalState;
+use anyhow::Result;
+use ethers::types::Address;
+use op_challenger_solvers::fault::AlphabetGame;
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    sync::Arc,
+};
+use tokio::sync::Mutex;
+
+/// The [StateStore] trait defines the interface for a storage backend of the [GlobalState].
+pub trait StateStore: Send + Sync {
+    /// Load the persisted [GlobalState].
+    ///
+    /// ### Returns
+    /// - `Ok(Some(GlobalState))`: The persisted state.
+    /// - `Ok(None)`: No state has been persisted yet.
+    /// - `Err(anyhow::Error)`: An error occurred while loading the state.
+    fn load(&self) -> Result<Option<GlobalState>>;
+
+    /// Persist the given [GlobalState], replacing any previously persisted state. The claim data
+    /// of the tracked games is persisted separately with [StateStore::save_game]; only games
+    /// that are no longer tracked are removed.
+    ///
+    /// ### Takes
+    /// - `state`: The state to persist.
+    ///
+    /// ### Returns
+    /// - `Ok(())`: The state was persisted.
+    /// - `Err(anyhow::Error)`: An error occurred while persisting the state.
+    fn save(&self, state: &GlobalState) -> Result<()>;
+
+    /// Persist a single [AlphabetGame], replacing any previously persisted state of the game.
+    ///
+    /// ### Takes
+    /// - `game`: The game to persist.
+    ///
+    /// ### Returns
+    /// - `Ok(())`: The game was persisted.
+    /// - `Err(anyhow::Error)`: An error occurred while persisting the game.
+    fn save_game(&self, game: &AlphabetGame) -> Result<()>;
+}
+
+/// The [MemoryStore] does not persist anything. The [GlobalState] only lives in memory, and is
+/// lost when the `op-challenger` exits.
+#[derive(Debug, Default)]
+pub struct MemoryStore;
+
+impl StateStore for MemoryStore {
+    fn load(&self) -> Result<Option<GlobalState>> {
+        Ok(None)
+    }
+
+    fn save(&self, _: &GlobalState) -> Result<()> {
+        Ok(())
+    }
+
+    fn save_game(&self, _: &AlphabetGame) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// The [SledStore] persists the [GlobalState] to an embedded [sled] database. Each game is stored
+/// under its address, so that a game's claim data can be inspected without loading every game.
+#[derive(Debug)]
+pub struct SledStore {
+    /// The tree holding the [AlphabetGame]s, keyed by their address.
+    alphabet_games: sled::Tree,
+    /// The default tree, holding the remaining fields of the [GlobalState].
+    meta: sled::Db,
+}
+
+impl SledStore {
+    /// The name of the tree holding the [AlphabetGame]s.
+    const ALPHABET_GAMES_TREE: &'static str = "alphabet_games";
+    /// The key of the last processed L1 block number.
+    const LAST_L1_BLOCK_KEY: &'static str = "last_l1_block";
+    /// The key of the last L1 block number whose `OutputProposed` events have been processed.
+    const LAST_OUTPUT_BLOCK_KEY: &'static str = "last_output_block";
+    /// The key of the origin blocks of the tracked games.
+    const GAME_ORIGINS_KEY: &'static str = "game_origins";
+
+    /// Opens the database at the given path, creating it if it does not exist.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        // Every save is flushed explicitly. The background flusher would also hold the database
+        // lock for a moment after the store is dropped.
+        let db = sled::Config::new().path(path).flush_every_ms(None).open()?;
+        Ok(Self {
+            alphabet_games: db.open_tree(Self::ALPHABET_GAMES_TREE)?,
+            meta: db,
+        })
+    }
+}
+
+impl StateStore for SledStore {
+    fn load(&self) -> Result<Option<GlobalState>> {
+        let last_l1_block = self.meta.get(Self::LAST_L1_BLOCK_KEY)?;
+        let last_output_block = self.meta.get(Self::LAST_OUTPUT_BLOCK_KEY)?;
+        if last_l1_block.is_none() && last_output_block.is_none() && self.alphabet_games.is_empty()
+        {
+            return Ok(None);
+        }
+
+        let alphabet_games = self
+            .alphabet_games
+            .iter()
+            .values()
+            .map(|game| {
+                let game = serde_json::from_slice::<AlphabetGame>(&game?)?;
+                Ok((game.address, Arc::new(Mutex::new(game))))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+
+        Ok(Some(GlobalState {
+            alphabet_games,
+            last_l1_block: last_l1_block
+                .map(|block| serde_json::from_slice(&block))
+                .transpose()?,
+            last_output_block: last_output_block
+                .map(|block| serde_json::from_slice(&block))
+                .transpose()?,
+            game_origins: self
+                .meta
+                .get(Self::GAME_ORIGINS_KEY)?
+                .map(|origins| serde_json::from_slice(&origins))
+                .transpose()?
+                .unwrap_or_default(),
+            stale_games: HashSet::new(),
+            simulated_resolutions: HashSet::new(),
+        }))
+    }
+
+    fn save(&self, state: &GlobalState) -> Result<()> {
+        let mut batch = sled::Batch::default();
+        for key in self.alphabet_games.iter().keys() {
+            let key = key?;
+            if !state
+                .alphabet_games
+                .contains_key(&Address::from_slice(&key))
+            {
+                batch.remove(key);
+            }
+        }
+        self.alphabet_games.apply_batch(batch)?;
+
+        match state.last_l1_block {
+            Some(block) => self
+                .meta
+                .insert(Self::LAST_L1_BLOCK_KEY, serde_json::to_vec(&block)?)?,
+            None => self.meta.remove(Self::LAST_L1_BLOCK_KEY)?,
+        };
+        match state.last_output_block {
+            Some(block) => self
+                .meta
+                .insert(Self::LAST_OUTPUT_BLOCK_KEY, serde_json::to_vec(&block)?)?,
+            None => self.meta.remove(Self::LAST_OUTPUT_BLOCK_KEY)?,
+        };
+        self.meta.insert(
+            Self::GAME_ORIGINS_KEY,
+            serde_json::to_vec(&state.game_origins)?,
+        )?;
+        self.meta.flush()?;
+        Ok(())
+    }
+
+    fn save_game(&self, game: &AlphabetGame) -> Result<()> {
+        self.alphabet_games
+            .insert(game.address.as_bytes(), serde_json::to_vec(game)?)?;
+        self.meta.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SledStore, StateStore};
+    use crate::{GlobalState, L1BlockRef};
+    use ethers::types::{Address, H256};
+    use op_challenger_solvers::fault::{
+        providers::AlphabetTraceProvider, AlphabetGame, Claim, ClaimData, Clock,
+    };
+    use std::{collections::HashMap, sync::Arc};
+    use tokio::sync::Mutex;
+
+    /// Creates a new [AlphabetGame] at the given address with a single root claim.
+    async fn game(address: Address, created_at: u64) -> AlphabetGame {
+        let mut game = AlphabetGame::new(
+            address,
+            created_at,
+            4,
+            Claim::from_low_u64_be(96),
+            100,
+            AlphabetTraceProvider::new(Arc::new(*b"abcdefghijklmnop"), 4).unwrap(),
+        )
+        .await
+        .unwrap();
+        game.state.push(ClaimData {
+            parent_index: u32::MAX as usize,
+            countered: false,
+            claim: Claim::repeat_byte(1),
+            position: 1,
+            clock: Clock {
+                duration: 0,
+                timestamp: created_at,
+            },
+        });
+        game
+    }
+
+    #[tokio::test]
+    async fn sled_store_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SledStore::open(dir.path()).unwrap();
+        assert!(store.load().unwrap().is_none());
+
+        let games = [
+            game(Address::repeat_byte(2), 1).await,
+            game(Address::repeat_byte(1), 2).await,
+        ];
+        for game in games.iter() {
+            store.save_game(game).unwrap();
+        }
+        let expected = games[1].state.clone();
+        let state = GlobalState {
+            alphabet_games: games
+                .into_iter()
+                .map(|game| (game.address, Arc::new(Mutex::new(game))))
+                .collect(),
+            last_l1_block: Some(100),
+            last_output_block: Some(98),
+            game_origins: HashMap::from([(
+                Address::repeat_byte(1),
+                L1BlockRef {
+                    number: 99,
+                    hash: H256::repeat_byte(9),
+                },
+            )]),
+            ..Default::default()
+        };
+        store.save(&state).unwrap();
+        drop(store);
+
+        // The state survives reopening the database.
+        let store = SledStore::open(dir.path()).unwrap();
+        let loaded = store.load().unwrap().unwrap();
+        assert_eq!(loaded.last_l1_block, Some(100));
+        assert_eq!(loaded.last_output_block, Some(98));
+        assert_eq!(loaded.game_origins, state.game_origins);
+        assert_eq!(loaded.alphabet_games.len(), 2);
+        assert_eq!(
+            loaded.alphabet_games[&Address::repeat_byte(1)]
+                .lock()
+                .await
+                .state,
+            expected
+        );
+
+        // Games that are no longer in the state are removed.
+        let game = game(Address::repeat_byte(3), 3).await;
+        store.save_game(&game).unwrap();
+        store
+            .save(&GlobalState {
+                alphabet_games: HashMap::from([(game.address, Arc::new(Mutex::new(game)))]),
+                last_l1_block: Some(101),
+                ..Default::default()
+            })
+            .unwrap();
+        let loaded = store.load().unwrap().unwrap();
+        assert_eq!(loaded.alphabet_games.len(), 1);
+        assert!(loaded.alphabet_games.contains_key(&Address::repeat_byte(3)));
+    }
+}
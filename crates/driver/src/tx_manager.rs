@@ -0,0 +1,610 @@
+//! The tx_manager module contains the [TxManager], which assigns nonces and EIP-1559 fees to
+//! outgoing transactions, and replaces them with bumped fees until they are included.
+
+use crate::{GasBumpPolicy, SignerMiddlewareWS, TxOutcome, TxRequest, TxRevert};
+use anyhow::{anyhow, Result};
+use ethers::{
+    providers::{Middleware, MiddlewareError},
+    types::{
+        transaction::eip2718::TypedTransaction, Address, BlockNumber, Eip1559TransactionRequest,
+        TransactionReceipt, H256, U256,
+    },
+};
+use std::{collections::BTreeMap, sync::Arc, time::Instant};
+use tokio::sync::oneshot;
+
+/// The maximum number of times that a caller dispatches a transaction for the same purpose,
+/// re-planning it after each attempt that was not included.
+pub(crate) const MAX_TX_ATTEMPTS: usize = 3;
+
+/// A transaction that has been broadcast, but not yet included in a block.
+struct PendingTx {
+    /// The transaction, as most recently broadcast.
+    tx: Eip1559TransactionRequest,
+    /// The hashes of every broadcast version of the transaction, oldest first.
+    hashes: Vec<H256>,
+    /// The time at which the transaction was last broadcast.
+    sent_at: Instant,
+    /// The sending handle of the channel that the [TxOutcome] is reported to.
+    outcome: oneshot::Sender<TxOutcome>,
+}
+
+/// The [TxManager] sends transactions from the L1 signer. It assigns nonces locally, so that
+/// transactions sent in quick succession do not collide, and tracks every transaction until it
+/// is included. Transactions that are stuck are replaced with bumped fees according to the
+/// [GasBumpPolicy]. Transactions are sent from the default sender of the client.
+pub(crate) struct TxManager<M = SignerMiddlewareWS> {
+    /// The L1 provider, which signs the transactions.
+    client: Arc<M>,
+    /// The policy for replacing stuck transactions.
+    policy: GasBumpPolicy,
+    /// The nonce of the next transaction. Fetched from the provider if unknown.
+    next_nonce: Option<U256>,
+    /// The pending transactions, keyed by their nonce.
+    pending: BTreeMap<U256, PendingTx>,
+}
+
+impl<M: Middleware + 'static> TxManager<M> {
+    /// Creates a new [TxManager] with the given provider and [GasBumpPolicy].
+    pub(crate) fn new(client: Arc<M>, policy: GasBumpPolicy) -> Self {
+        Self {
+            client,
+            policy,
+            next_nonce: None,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    /// Simulates the requested transaction, assigns it the next nonce and EIP-1559 fees, and
+    /// broadcasts it. If the transaction reverts in simulation, it is reported as
+    /// [TxOutcome::Rejected] with the decoded [TxRevert]. If it fails to simulate or to
+    /// broadcast for any other reason, it is reported as [TxOutcome::Dropped].
+    ///
+    /// ### Takes
+    /// - `request`: The [TxRequest] to send.
+    ///
+    /// ### Returns
+    /// - `Ok(H256)`: The hash of the broadcast transaction.
+    /// - `Err(anyhow::Error)`: The transaction failed to simulate or to broadcast. No nonce is
+    ///   consumed.
+    pub(crate) async fn send(&mut self, request: TxRequest) -> Result<H256> {
+        let TxRequest { tx, outcome, .. } = request;
+        let gas = match self.client.estimate_gas(&tx, None).await {
+            Ok(gas) => gas,
+            Err(e) => {
+                let revert = e
+                    .as_error_response()
+                    .and_then(|e| e.as_revert_data())
+                    .map(|data| TxRevert::decode(&data));
+                return Err(match revert {
+                    Some(revert) => {
+                        let e = anyhow!(
+                            "transaction reverted in simulation with reason {} (action: {:?})",
+                            revert,
+                            revert.action()
+                        );
+                        let _ = outcome.send(TxOutcome::Rejected(revert));
+                        e
+                    }
+                    None => {
+                        let _ = outcome.send(TxOutcome::Dropped(e.to_string()));
+                        e.into()
+                    }
+                });
+            }
+        };
+        match self.try_send(tx, gas).await {
+            Ok((nonce, tx, hash)) => {
+                self.pending.insert(
+                    nonce,
+                    PendingTx {
+                        tx,
+                        hashes: vec![hash],
+                        sent_at: Instant::now(),
+                        outcome,
+                    },
+                );
+                Ok(hash)
+            }
+            Err(e) => {
+                let _ = outcome.send(TxOutcome::Dropped(e.to_string()));
+                Err(e)
+            }
+        }
+    }
+
+    /// Broadcasts the given transaction with the given gas limit, returning its nonce, the
+    /// [Eip1559TransactionRequest] that was sent and its hash.
+    async fn try_send(
+        &mut self,
+        tx: TypedTransaction,
+        gas: U256,
+    ) -> Result<(U256, Eip1559TransactionRequest, H256)> {
+        let (max_fee, priority_fee) = self.client.estimate_eip1559_fees(None).await?;
+        if max_fee > self.policy.max_fee_per_gas {
+            tracing::warn!(target: "tx-dispatch-driver", "Estimated max fee per gas {} exceeds the cap of {}. Sending with the cap.", max_fee, self.policy.max_fee_per_gas);
+        }
+
+        let sender = self.sender()?;
+        let nonce = match self.next_nonce {
+            Some(nonce) => nonce,
+            None => {
+                self.client
+                    .get_transaction_count(sender, Some(BlockNumber::Pending.into()))
+                    .await?
+            }
+        };
+
+        // The chain ID is filled in by the signer.
+        let mut request = Eip1559TransactionRequest::new()
+            .from(sender)
+            .nonce(nonce)
+            .gas(gas)
+            .max_fee_per_gas(max_fee.min(self.policy.max_fee_per_gas))
+            .max_priority_fee_per_gas(priority_fee.min(self.policy.max_fee_per_gas));
+        if let Some(to) = tx.to() {
+            request = request.to(to.clone());
+        }
+        if let Some(data) = tx.data() {
+            request = request.data(data.clone());
+        }
+        if let Some(value) = tx.value() {
+            request = request.value(*value);
+        }
+
+        let hash = match Self::broadcast(&self.client, &request).await {
+            Ok(hash) => hash,
+            Err(e) => {
+                // The nonce may be out of sync with the chain; fetch it again for the next
+                // transaction.
+                self.next_nonce = None;
+                return Err(e);
+            }
+        };
+        self.next_nonce = Some(nonce + 1);
+        Ok((nonce, request, hash))
+    }
+
+    /// Stops tracking the pending transactions that have been included, and replaces the ones
+    /// that have been pending for longer than the [GasBumpPolicy] allows with bumped fees.
+    pub(crate) async fn check_pending(&mut self) -> Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+
+        // Transactions with a nonce below the confirmed nonce have been included, either as
+        // sent or as one of their replacements.
+        let confirmed_nonce = self
+            .client
+            .get_transaction_count(self.sender()?, Some(BlockNumber::Latest.into()))
+            .await?;
+        let mut included = self.pending.split_off(&confirmed_nonce);
+        std::mem::swap(&mut included, &mut self.pending);
+        for (nonce, tx) in included {
+            let mut receipt = None;
+            for hash in tx.hashes.iter().rev() {
+                receipt = self.client.get_transaction_receipt(*hash).await?;
+                if receipt.is_some() {
+                    break;
+                }
+            }
+            let outcome = match receipt {
+                Some(receipt) if receipt.status == Some(1.into()) => TxOutcome::Included(receipt),
+                Some(receipt) => {
+                    let reason = self.revert_reason(&tx.tx, &receipt).await;
+                    TxOutcome::Reverted(receipt, reason)
+                }
+                None => TxOutcome::Dropped(format!(
+                    "nonce {} was used by a transaction that was not sent by this process",
+                    nonce
+                )),
+            };
+            if outcome.is_included() {
+                tracing::info!(target: "tx-dispatch-driver", "Transaction with nonce {} {}", nonce, outcome);
+            } else {
+                tracing::error!(target: "tx-dispatch-driver", "Transaction with nonce {} {}", nonce, outcome);
+            }
+            let _ = tx.outcome.send(outcome);
+        }
+
+        // Replace the transactions that are stuck.
+        for (nonce, pending) in self.pending.iter_mut() {
+            if pending.sent_at.elapsed() < self.policy.bump_after {
+                continue;
+            }
+            pending.sent_at = Instant::now();
+
+            let cap = self.policy.max_fee_per_gas;
+            let max_fee = pending.tx.max_fee_per_gas.unwrap_or_default();
+            if max_fee >= cap {
+                // The fees can not be bumped any further. The transaction is only broadcast
+                // again if it was evicted from the mempool.
+                let last_hash = pending.hashes.last().copied().unwrap_or_default();
+                if self.client.get_transaction(last_hash).await?.is_some() {
+                    tracing::warn!(target: "tx-dispatch-driver", "Transaction with nonce {} is stuck, but its max fee per gas is already at the cap of {}.", nonce, cap);
+                    continue;
+                }
+                tracing::warn!(target: "tx-dispatch-driver", "Transaction with nonce {} was evicted from the mempool with its max fee per gas at the cap of {}. Broadcasting it again...", nonce, cap);
+            } else {
+                // Bump both fees by at least the configured percentage, and at least up to the
+                // current estimates.
+                let (estimated_max_fee, estimated_priority_fee) =
+                    self.client.estimate_eip1559_fees(None).await?;
+                let max_fee = self.policy.bump(max_fee).max(estimated_max_fee).min(cap);
+                let priority_fee = self
+                    .policy
+                    .bump(pending.tx.max_priority_fee_per_gas.unwrap_or_default())
+                    .max(estimated_priority_fee)
+                    .min(max_fee);
+                pending.tx.max_fee_per_gas = Some(max_fee);
+                pending.tx.max_priority_fee_per_gas = Some(priority_fee);
+            }
+
+            match Self::broadcast(&self.client, &pending.tx).await {
+                Ok(hash) => {
+                    tracing::info!(target: "tx-dispatch-driver", "Replaced stuck transaction with nonce {} (max fee per gas: {}, priority fee per gas: {}). Tx hash: {:?}", nonce, pending.tx.max_fee_per_gas.unwrap_or_default(), pending.tx.max_priority_fee_per_gas.unwrap_or_default(), hash);
+                    // A transaction that is broadcast again unchanged keeps its hash.
+                    if !pending.hashes.contains(&hash) {
+                        pending.hashes.push(hash);
+                    }
+                }
+                Err(e) => {
+                    // The transaction may have been included in the meantime, which is picked
+                    // up on the next check.
+                    tracing::error!(target: "tx-dispatch-driver", "Error replacing transaction with nonce {}: {}", nonce, e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decodes the revert of a reverted transaction by replaying it against the parent of the
+    /// block it was included in.
+    async fn revert_reason(
+        &self,
+        tx: &Eip1559TransactionRequest,
+        receipt: &TransactionReceipt,
+    ) -> Option<TxRevert> {
+        let block = receipt.block_number?.checked_sub(1.into())?;
+        let replay = self
+            .client
+            .call(&TypedTransaction::Eip1559(tx.clone()), Some(block.into()))
+            .await;
+        match replay {
+            Ok(_) => None,
+            Err(e) => e
+                .as_error_response()
+                .and_then(|e| e.as_revert_data())
+                .map(|data| TxRevert::decode(&data)),
+        }
+    }
+
+    /// Returns the address that the transactions are sent from.
+    fn sender(&self) -> Result<Address> {
+        self.client
+            .default_sender()
+            .ok_or(anyhow!("The L1 provider has no default sender"))
+    }
+
+    /// Signs and broadcasts the given transaction, returning its hash.
+    async fn broadcast(client: &M, tx: &Eip1559TransactionRequest) -> Result<H256> {
+        Ok(client
+            .send_transaction(TypedTransaction::Eip1559(tx.clone()), None)
+            .await?
+            .tx_hash())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TxManager;
+    use crate::{
+        bindings::fault_dispute_game, GasBumpPolicy, TxKind, TxMeta, TxOutcome, TxRequest, TxRevert,
+    };
+    use ethers::{
+        abi::AbiEncode,
+        providers::{JsonRpcClient, JsonRpcError, MockProvider, MockResponse, Provider},
+        types::{
+            transaction::eip2718::TypedTransaction, Address, Block, Bytes,
+            Eip1559TransactionRequest, FeeHistory, Transaction, TransactionReceipt, H256, U256,
+        },
+        utils::eip1559_default_estimator,
+    };
+    use op_challenger_solvers::fault::GameError;
+    use serde::Serialize;
+    use std::{sync::Arc, time::Duration};
+    use tokio::sync::oneshot;
+
+    /// The base fee per gas reported by the mock, in wei.
+    const BASE_FEE: u64 = 1_000_000_000;
+
+    /// Creates a [TxManager] backed by a [MockProvider], which replaces every pending
+    /// transaction on each check, and caps fees at the given max fee per gas.
+    fn tx_manager(max_fee_per_gas: U256) -> (TxManager<Provider<MockProvider>>, MockProvider) {
+        let (provider, mock) = Provider::mocked();
+        let policy = GasBumpPolicy {
+            bump_after: Duration::ZERO,
+            bump_percent: 20,
+            max_fee_per_gas,
+        };
+        let client = Arc::new(provider.with_sender(Address::repeat_byte(0xaa)));
+        (TxManager::new(client, policy), mock)
+    }
+
+    /// Creates a new [TxRequest], along with the receiving handle of its [TxOutcome].
+    fn tx_request() -> (TxRequest, oneshot::Receiver<TxOutcome>) {
+        let tx = Eip1559TransactionRequest::new().to(Address::repeat_byte(1));
+        TxRequest::new(TypedTransaction::Eip1559(tx), TxMeta::new(TxKind::Move))
+    }
+
+    /// Queues the given responses on the mock, to be served in order.
+    fn respond(mock: &MockProvider, responses: Vec<MockResponse>) {
+        for response in responses.into_iter().rev() {
+            mock.push_response(response);
+        }
+    }
+
+    /// Asserts that every response queued on the mock has been served.
+    async fn assert_served(mock: &MockProvider) {
+        assert!(mock
+            .request::<_, serde_json::Value>("eth_chainId", ())
+            .await
+            .is_err());
+    }
+
+    /// Returns a successful response with the given value.
+    fn value<T: Serialize>(value: T) -> MockResponse {
+        MockResponse::Value(serde_json::to_value(value).unwrap())
+    }
+
+    /// Returns an error response of a call that reverted with the given data.
+    fn revert(data: Bytes) -> MockResponse {
+        MockResponse::Error(JsonRpcError {
+            code: 3,
+            message: "execution reverted".to_string(),
+            data: Some(serde_json::Value::String(data.to_string())),
+        })
+    }
+
+    /// Returns a receipt of the transaction with the given hash, included in block 10.
+    fn receipt(hash: u8, status: u64) -> TransactionReceipt {
+        TransactionReceipt {
+            transaction_hash: H256::repeat_byte(hash),
+            block_number: Some(10.into()),
+            status: Some(status.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the responses to an EIP-1559 fee estimate: the latest block, and the fee history.
+    fn fees() -> Vec<MockResponse> {
+        vec![
+            value(Block::<H256> {
+                base_fee_per_gas: Some(BASE_FEE.into()),
+                ..Default::default()
+            }),
+            value(FeeHistory {
+                base_fee_per_gas: vec![],
+                gas_used_ratio: vec![],
+                oldest_block: U256::zero(),
+                reward: vec![],
+            }),
+        ]
+    }
+
+    /// Returns the responses to sending a transaction: the gas estimate, the fee estimate, the
+    /// pending nonce if it is fetched, and the hash of the broadcast transaction.
+    fn send(nonce: Option<u64>, hash: MockResponse) -> Vec<MockResponse> {
+        let mut responses = vec![value(U256::from(21_000))];
+        responses.extend(fees());
+        responses.extend(nonce.map(|nonce| value(U256::from(nonce))));
+        responses.push(hash);
+        responses
+    }
+
+    #[tokio::test]
+    async fn reuses_nonces_for_replacements() {
+        let (mut tx_manager, mock) = tx_manager(U256::exp10(12));
+        let (estimated_max_fee, _) = eip1559_default_estimator(BASE_FEE.into(), vec![]);
+
+        // The nonce is only fetched for the first transaction.
+        respond(&mock, send(Some(5), value(H256::repeat_byte(1))));
+        tx_manager.send(tx_request().0).await.unwrap();
+        respond(&mock, send(None, value(H256::repeat_byte(2))));
+        tx_manager.send(tx_request().0).await.unwrap();
+        assert_served(&mock).await;
+
+        // Neither transaction is included, so both are replaced with bumped fees, keeping their
+        // nonces.
+        let mut responses = vec![value(U256::from(5))];
+        responses.extend(fees());
+        responses.push(value(H256::repeat_byte(3)));
+        responses.extend(fees());
+        responses.push(value(H256::repeat_byte(4)));
+        respond(&mock, responses);
+        tx_manager.check_pending().await.unwrap();
+        assert_served(&mock).await;
+
+        for (nonce, hashes) in [(5, [1, 3]), (6, [2, 4])] {
+            let pending = &tx_manager.pending[&U256::from(nonce)];
+            assert_eq!(pending.tx.nonce, Some(U256::from(nonce)));
+            assert_eq!(pending.hashes, hashes.map(H256::repeat_byte));
+            assert_eq!(
+                pending.tx.max_fee_per_gas,
+                Some(tx_manager.policy.bump(estimated_max_fee))
+            );
+        }
+        assert_eq!(tx_manager.next_nonce, Some(U256::from(7)));
+    }
+
+    #[tokio::test]
+    async fn refetches_nonce_after_failed_broadcast() {
+        let (mut tx_manager, mock) = tx_manager(U256::exp10(12));
+
+        respond(
+            &mock,
+            send(
+                Some(5),
+                MockResponse::Error(JsonRpcError {
+                    code: -32000,
+                    message: "nonce too low".to_string(),
+                    data: None,
+                }),
+            ),
+        );
+        let (request, outcome) = tx_request();
+        assert!(tx_manager.send(request).await.is_err());
+        assert!(matches!(outcome.await, Ok(TxOutcome::Dropped(_))));
+        assert_eq!(tx_manager.next_nonce, None);
+        assert!(tx_manager.pending.is_empty());
+
+        // The nonce is fetched again for the next transaction.
+        respond(&mock, send(Some(7), value(H256::repeat_byte(1))));
+        tx_manager.send(tx_request().0).await.unwrap();
+        assert_served(&mock).await;
+        assert!(tx_manager.pending.contains_key(&U256::from(7)));
+        assert_eq!(tx_manager.next_nonce, Some(U256::from(8)));
+    }
+
+    #[tokio::test]
+    async fn reports_inclusion_of_replaced_transactions() {
+        let (mut tx_manager, mock) = tx_manager(U256::exp10(12));
+        let (request, outcome) = tx_request();
+        respond(&mock, send(Some(5), value(H256::repeat_byte(1))));
+        tx_manager.send(request).await.unwrap();
+
+        let mut responses = vec![value(U256::from(5))];
+        responses.extend(fees());
+        responses.push(value(H256::repeat_byte(2)));
+        respond(&mock, responses);
+        tx_manager.check_pending().await.unwrap();
+
+        // The original transaction is included instead of its replacement. The receipts are
+        // looked up newest first.
+        let receipt = receipt(1, 1);
+        respond(
+            &mock,
+            vec![
+                value(U256::from(6)),
+                value(serde_json::Value::Null),
+                value(&receipt),
+            ],
+        );
+        tx_manager.check_pending().await.unwrap();
+        assert_served(&mock).await;
+        assert!(tx_manager.pending.is_empty());
+        match outcome.await {
+            Ok(TxOutcome::Included(included)) => assert_eq!(included, receipt),
+            outcome => panic!("Unexpected outcome: {:?}", outcome),
+        }
+    }
+
+    #[tokio::test]
+    async fn stops_bumping_at_the_fee_cap() {
+        let cap = U256::from(BASE_FEE);
+        let (mut tx_manager, mock) = tx_manager(cap);
+
+        // The estimated fees exceed the cap, so the transaction is sent with the cap.
+        respond(&mock, send(Some(0), value(H256::repeat_byte(1))));
+        tx_manager.send(tx_request().0).await.unwrap();
+        let pending = &tx_manager.pending[&U256::zero()];
+        assert_eq!(pending.tx.max_fee_per_gas, Some(cap));
+        assert_eq!(pending.tx.max_priority_fee_per_gas, Some(cap));
+
+        // While the transaction is in the mempool, it is left alone.
+        respond(
+            &mock,
+            vec![
+                value(U256::zero()),
+                value(Transaction {
+                    hash: H256::repeat_byte(1),
+                    ..Default::default()
+                }),
+            ],
+        );
+        tx_manager.check_pending().await.unwrap();
+        assert_served(&mock).await;
+
+        // Once it is evicted, it is broadcast again unchanged.
+        respond(
+            &mock,
+            vec![
+                value(U256::zero()),
+                value(serde_json::Value::Null),
+                value(H256::repeat_byte(1)),
+            ],
+        );
+        tx_manager.check_pending().await.unwrap();
+        assert_served(&mock).await;
+        let pending = &tx_manager.pending[&U256::zero()];
+        assert_eq!(pending.hashes, vec![H256::repeat_byte(1)]);
+        assert_eq!(pending.tx.max_fee_per_gas, Some(cap));
+    }
+
+    #[tokio::test]
+    async fn maps_receipts_to_outcomes() {
+        let (mut tx_manager, mock) = tx_manager(U256::exp10(12));
+        let mut outcomes = Vec::new();
+        for (nonce, hash) in [(Some(5), 1), (None, 2), (None, 3)] {
+            let (request, outcome) = tx_request();
+            respond(&mock, send(nonce, value(H256::repeat_byte(hash))));
+            tx_manager.send(request).await.unwrap();
+            outcomes.push(outcome);
+        }
+
+        // Every transaction was included. The revert of the second transaction is decoded by
+        // replaying it, and the third transaction's nonce was used by another transaction.
+        respond(
+            &mock,
+            vec![
+                value(U256::from(8)),
+                value(receipt(1, 1)),
+                value(receipt(2, 0)),
+                revert(fault_dispute_game::ClaimAlreadyExists.encode().into()),
+                value(serde_json::Value::Null),
+            ],
+        );
+        tx_manager.check_pending().await.unwrap();
+        assert_served(&mock).await;
+        assert!(tx_manager.pending.is_empty());
+
+        let mut outcomes = outcomes.into_iter();
+        match outcomes.next().unwrap().await {
+            Ok(TxOutcome::Included(included)) => assert_eq!(included, receipt(1, 1)),
+            outcome => panic!("Unexpected outcome: {:?}", outcome),
+        }
+        match outcomes.next().unwrap().await {
+            Ok(TxOutcome::Reverted(reverted, revert)) => {
+                assert_eq!(reverted, receipt(2, 0));
+                assert_eq!(revert, Some(TxRevert::Game(GameError::ClaimAlreadyExists)));
+            }
+            outcome => panic!("Unexpected outcome: {:?}", outcome),
+        }
+        assert!(matches!(
+            outcomes.next().unwrap().await,
+            Ok(TxOutcome::Dropped(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn rejects_transactions_that_revert_in_simulation() {
+        let (mut tx_manager, mock) = tx_manager(U256::exp10(12));
+        let (request, outcome) = tx_request();
+        respond(
+            &mock,
+            vec![revert(
+                fault_dispute_game::ClaimAlreadyExists.encode().into(),
+            )],
+        );
+        assert!(tx_manager.send(request).await.is_err());
+        assert_served(&mock).await;
+        assert!(tx_manager.pending.is_empty());
+        match outcome.await {
+            Ok(TxOutcome::Rejected(revert)) => {
+                assert_eq!(revert, TxRevert::Game(GameError::ClaimAlreadyExists))
+            }
+            outcome => panic!("Unexpected outcome: {:?}", outcome),
+        }
+    }
+}
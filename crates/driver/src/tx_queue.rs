@@ -0,0 +1,253 @@
+//! The tx_queue module contains the [TxQueue], which orders the transactions dispatched to the
+//! [TxDispatchDriver](crate::TxDispatchDriver) by urgency.
+
+use crate::{TxOutcome, TxRequest};
+use std::{
+    cmp::Ordering,
+    collections::BinaryHeap,
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::{Notify, Semaphore};
+
+/// A [TxRequest] in the [TxQueue].
+struct QueuedTx {
+    /// The request.
+    request: TxRequest,
+    /// The order in which the request was pushed, used to break ties.
+    seq: u64,
+}
+
+impl QueuedTx {
+    /// Returns the key that requests are ordered by. The greatest key is the most urgent: the
+    /// earliest deadline first, then the highest priority, then the oldest request. Requests
+    /// without a deadline come after all requests with one.
+    fn urgency(&self) -> (bool, std::cmp::Reverse<u64>, u8, std::cmp::Reverse<u64>) {
+        let meta = &self.request.meta;
+        (
+            meta.deadline.is_some(),
+            std::cmp::Reverse(meta.deadline.unwrap_or_default()),
+            meta.priority,
+            std::cmp::Reverse(self.seq),
+        )
+    }
+}
+
+impl PartialEq for QueuedTx {
+    fn eq(&self, other: &Self) -> bool {
+        self.seq == other.seq
+    }
+}
+
+impl Eq for QueuedTx {}
+
+impl PartialOrd for QueuedTx {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedTx {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.urgency().cmp(&other.urgency())
+    }
+}
+
+/// A snapshot of the metrics of a [TxQueue].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TxQueueMetrics {
+    /// The number of requests currently in the queue.
+    pub queued: usize,
+    /// The total number of requests pushed to the queue.
+    pub pushed: u64,
+    /// The total number of requests dropped because their deadline passed while they were
+    /// queued.
+    pub dropped_stale: u64,
+    /// The total number of pushes that had to wait for the queue to have capacity.
+    pub backpressure_waits: u64,
+    /// The total time spent waiting for the queue to have capacity.
+    pub backpressure_time: Duration,
+}
+
+/// The mutable state of a [TxQueue].
+#[derive(Default)]
+struct TxQueueState {
+    /// The queued requests.
+    heap: BinaryHeap<QueuedTx>,
+    /// The metrics of the queue.
+    metrics: TxQueueMetrics,
+}
+
+/// The [TxQueue] is a bounded priority queue of [TxRequest]s. The most urgent request is always
+/// dispatched first, and requests whose deadline has passed are dropped instead of dispatched.
+/// Pushing to a full queue waits until a request is popped.
+pub struct TxQueue {
+    /// The state of the queue.
+    state: Mutex<TxQueueState>,
+    /// Notifies the consumer of new requests.
+    notify: Notify,
+    /// The remaining capacity of the queue.
+    capacity: Semaphore,
+}
+
+impl TxQueue {
+    /// Creates a new [TxQueue] that holds at most `capacity` requests.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            state: Mutex::new(TxQueueState::default()),
+            notify: Notify::new(),
+            capacity: Semaphore::new(capacity),
+        }
+    }
+
+    /// Pushes a request to the queue, waiting for capacity if the queue is full.
+    pub async fn push(&self, request: TxRequest) {
+        let permit = match self.capacity.try_acquire() {
+            Ok(permit) => permit,
+            Err(_) => {
+                let start = Instant::now();
+                let permit = self
+                    .capacity
+                    .acquire()
+                    .await
+                    .expect("the queue's semaphore is never closed");
+                let mut state = self.lock();
+                state.metrics.backpressure_waits += 1;
+                state.metrics.backpressure_time += start.elapsed();
+                permit
+            }
+        };
+        // The permit is returned when the request is popped.
+        permit.forget();
+
+        let mut state = self.lock();
+        let seq = state.metrics.pushed;
+        state.metrics.pushed += 1;
+        state.heap.push(QueuedTx { request, seq });
+        drop(state);
+        self.notify.notify_one();
+    }
+
+    /// Pops the most urgent request from the queue, waiting for one if the queue is empty.
+    /// Requests whose deadline has passed are reported as [TxOutcome::Dropped] and skipped.
+    pub async fn pop(&self) -> TxRequest {
+        loop {
+            let notified = self.notify.notified();
+            if let Some(request) = self.try_pop() {
+                return request;
+            }
+            notified.await;
+        }
+    }
+
+    /// Returns a snapshot of the metrics of the queue.
+    pub fn metrics(&self) -> TxQueueMetrics {
+        let state = self.lock();
+        TxQueueMetrics {
+            queued: state.heap.len(),
+            ..state.metrics
+        }
+    }
+
+    /// Pops the most urgent request whose deadline has not passed, if there is one.
+    fn try_pop(&self) -> Option<TxRequest> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let mut state = self.lock();
+        while let Some(QueuedTx { request, .. }) = state.heap.pop() {
+            self.capacity.add_permits(1);
+            match request.meta.deadline {
+                Some(deadline) if deadline <= now => {
+                    state.metrics.dropped_stale += 1;
+                    tracing::warn!(target: "tx-dispatch-driver", "Dropping {:?} transaction for game {:?}: its deadline {} has passed.", request.meta.kind, request.meta.game, deadline);
+                    let _ = request.outcome.send(TxOutcome::Dropped(format!(
+                        "deadline {} passed while queued",
+                        deadline
+                    )));
+                }
+                _ => return Some(request),
+            }
+        }
+        None
+    }
+
+    /// Locks the state of the queue. The lock is never held across an await point.
+    fn lock(&self) -> std::sync::MutexGuard<'_, TxQueueState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TxQueue;
+    use crate::{TxKind, TxMeta, TxOutcome, TxRequest};
+    use ethers::types::{transaction::eip2718::TypedTransaction, Address};
+
+    /// Creates a [TxRequest] with the given metadata, using the sender address as a label.
+    fn request(label: u8, meta: TxMeta) -> TxRequest {
+        let mut tx = TypedTransaction::default();
+        tx.set_from(Address::repeat_byte(label));
+        TxRequest::new(tx, meta).0
+    }
+
+    #[tokio::test]
+    async fn pops_most_urgent_first() {
+        let queue = TxQueue::new(8);
+        let far = u64::MAX - 1;
+        queue.push(request(1, TxMeta::new(TxKind::Resolve))).await;
+        queue
+            .push(request(2, TxMeta::new(TxKind::Move).deadline(far)))
+            .await;
+        queue
+            .push(request(3, TxMeta::new(TxKind::Move).deadline(far - 1)))
+            .await;
+        queue
+            .push(request(4, TxMeta::new(TxKind::Step).deadline(far)))
+            .await;
+        queue
+            .push(request(5, TxMeta::new(TxKind::CreateGame)))
+            .await;
+
+        let mut order = Vec::new();
+        for _ in 0..5 {
+            order.push(queue.pop().await.tx.from().unwrap().0[0]);
+        }
+        assert_eq!(order, vec![3, 4, 2, 5, 1]);
+        assert_eq!(queue.metrics().pushed, 5);
+        assert_eq!(queue.metrics().queued, 0);
+    }
+
+    #[tokio::test]
+    async fn drops_stale_requests() {
+        let queue = TxQueue::new(8);
+        let (stale, outcome) = TxRequest::new(
+            TypedTransaction::default(),
+            TxMeta::new(TxKind::Move).deadline(1),
+        );
+        queue.push(stale).await;
+        queue.push(request(1, TxMeta::new(TxKind::Resolve))).await;
+
+        assert_eq!(queue.pop().await.tx.from().unwrap().0[0], 1);
+        assert!(matches!(outcome.await, Ok(TxOutcome::Dropped(_))));
+        assert_eq!(queue.metrics().dropped_stale, 1);
+    }
+
+    #[tokio::test]
+    async fn waits_for_capacity() {
+        let queue = TxQueue::new(1);
+        queue.push(request(1, TxMeta::new(TxKind::Resolve))).await;
+
+        // The queue is full, so the next push waits until a request is popped.
+        let mut push = std::pin::pin!(queue.push(request(2, TxMeta::new(TxKind::Resolve))));
+        assert!(futures::poll!(&mut push).is_pending());
+        assert_eq!(queue.pop().await.tx.from().unwrap().0[0], 1);
+        push.await;
+
+        assert_eq!(queue.pop().await.tx.from().unwrap().0[0], 2);
+        assert_eq!(queue.metrics().backpressure_waits, 1);
+        assert_eq!(queue.metrics().pushed, 2);
+    }
+}
@@ -1,13 +1,21 @@
 use ethers::{
+    abi::AbiDecode,
     prelude::SignerMiddleware,
     providers::{Provider, Ws},
     signers::LocalWallet,
-    types::H256,
+    types::{
+        transaction::eip2718::TypedTransaction, Address, BlockNumber, Bytes, Log,
+        TransactionReceipt, H256, U256,
+    },
 };
+use op_challenger_solvers::fault::GameError;
 use serde::{Deserialize, Serialize};
+use std::{str::FromStr, time::Duration};
+use tokio::sync::oneshot;
 
 /// The [GameType] enum defines the different types of dispute games with cloneable
 /// implementations in the `DisputeGameFactory` contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum GameType {
     Fault = 0,
@@ -28,11 +36,611 @@ impl TryFrom<u8> for GameType {
     }
 }
 
+impl FromStr for GameType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fault" => Ok(GameType::Fault),
+            "validity" => Ok(GameType::Validity),
+            "output-attestation" => Ok(GameType::OutputAttestation),
+            _ => GameType::try_from(s.parse::<u8>().map_err(|_| {
+                anyhow::anyhow!(
+                    "Invalid game type {}; expected `fault`, `validity`, `output-attestation` or a number",
+                    s
+                )
+            })?),
+        }
+    }
+}
+
+/// The [L1BlockRef] struct is a reference to a block on L1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct L1BlockRef {
+    /// The number of the block.
+    pub number: u64,
+    /// The hash of the block.
+    pub hash: H256,
+}
+
+impl L1BlockRef {
+    /// Returns a reference to the block that the given [Log] was emitted in, if it has been
+    /// included in a block.
+    pub fn from_log(log: &Log) -> Option<Self> {
+        Some(Self {
+            number: log.block_number?.as_u64(),
+            hash: log.block_hash?,
+        })
+    }
+}
+
+/// The [ConfirmationDepth] enum defines when an L1 block is considered confirmed, and the events
+/// emitted in it may be acted upon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmationDepth {
+    /// The block is confirmed once it is the given number of blocks behind the latest block. A
+    /// depth of `0`, or `latest`, acts on events as soon as they are seen.
+    Blocks(u64),
+    /// The block is confirmed once it is `safe`.
+    Safe,
+    /// The block is confirmed once it is `finalized`.
+    Finalized,
+}
+
+impl Default for ConfirmationDepth {
+    fn default() -> Self {
+        ConfirmationDepth::Blocks(0)
+    }
+}
+
+impl ConfirmationDepth {
+    /// Returns the latest L1 block that satisfies the [ConfirmationDepth].
+    ///
+    /// ### Takes
+    /// - `latest`: The number of the latest L1 block. Without it, depths in blocks are relative
+    ///   to the `latest` block tag, which is only exact for a depth of `0`.
+    ///
+    /// ### Returns
+    /// - The number or tag of the latest confirmed L1 block.
+    pub fn confirmed_block(&self, latest: Option<u64>) -> BlockNumber {
+        match (self, latest) {
+            (ConfirmationDepth::Blocks(blocks), Some(latest)) => {
+                BlockNumber::Number(latest.saturating_sub(*blocks).into())
+            }
+            (ConfirmationDepth::Blocks(_), None) => BlockNumber::Latest,
+            (ConfirmationDepth::Safe, _) => BlockNumber::Safe,
+            (ConfirmationDepth::Finalized, _) => BlockNumber::Finalized,
+        }
+    }
+}
+
+impl FromStr for ConfirmationDepth {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "latest" => Ok(ConfirmationDepth::Blocks(0)),
+            "safe" => Ok(ConfirmationDepth::Safe),
+            "finalized" => Ok(ConfirmationDepth::Finalized),
+            _ => Ok(ConfirmationDepth::Blocks(s.parse().map_err(|_| {
+                anyhow::anyhow!(
+                    "Invalid confirmation depth {}; expected a number of blocks, `latest`, `safe` or `finalized`",
+                    s
+                )
+            })?)),
+        }
+    }
+}
+
+/// The [Confirmations] struct holds the [ConfirmationDepth] that each driver waits for before
+/// acting on L1 events.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Confirmations {
+    /// The depth of `DisputeGameCreated` events processed by the
+    /// [DisputeFactoryDriver](crate::DisputeFactoryDriver).
+    pub dispute_factory: ConfirmationDepth,
+    /// The depth of the L1 block that the [FaultGameWatcherDriver](crate::FaultGameWatcherDriver)
+    /// reads claim data at.
+    pub fault_game_watcher: ConfirmationDepth,
+    /// The depth of `OutputProposed` events processed by the output handlers.
+    pub output_proposals: ConfirmationDepth,
+}
+
+/// The [TxOutcome] enum describes what became of a transaction dispatched through the
+/// [TxDispatchDriver](crate::TxDispatchDriver).
+#[derive(Debug, Clone)]
+pub enum TxOutcome {
+    /// The transaction was included, and succeeded.
+    Included(TransactionReceipt),
+    /// The transaction was included, but reverted. The revert is decoded by replaying the
+    /// transaction, if possible.
+    Reverted(TransactionReceipt, Option<TxRevert>),
+    /// The transaction was never sent, because it reverted when simulated.
+    Rejected(TxRevert),
+    /// The transaction was never included. It failed to simulate or to broadcast, or its nonce
+    /// was used by another transaction.
+    Dropped(String),
+    /// The transaction was only simulated, because the
+    /// [TxDispatchDriver](crate::TxDispatchDriver) runs in dry-run mode. Holds the return data of
+    /// the simulation, or its revert reason.
+    Simulated(Result<Bytes, String>),
+}
+
+impl TxOutcome {
+    /// Returns whether the transaction was included and succeeded.
+    pub fn is_included(&self) -> bool {
+        matches!(self, TxOutcome::Included(_))
+    }
+
+    /// Returns whether the transaction is settled, and must not be retried: it was included, or
+    /// it was only simulated in dry-run mode.
+    pub fn is_final(&self) -> bool {
+        matches!(self, TxOutcome::Included(_) | TxOutcome::Simulated(_))
+    }
+
+    /// Returns the decoded revert of the transaction, if it reverted onchain or in simulation.
+    pub fn revert(&self) -> Option<&TxRevert> {
+        match self {
+            TxOutcome::Reverted(_, revert) => revert.as_ref(),
+            TxOutcome::Rejected(revert) => Some(revert),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for TxOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TxOutcome::Included(receipt) => write!(
+                f,
+                "included in block {:?} (tx hash: {:?})",
+                receipt.block_number, receipt.transaction_hash
+            ),
+            TxOutcome::Reverted(receipt, revert) => write!(
+                f,
+                "reverted in block {:?} with reason {} (tx hash: {:?})",
+                receipt.block_number,
+                revert
+                    .as_ref()
+                    .map_or("unknown".to_string(), ToString::to_string),
+                receipt.transaction_hash
+            ),
+            TxOutcome::Rejected(revert) => {
+                write!(f, "rejected in simulation with reason {}", revert)
+            }
+            TxOutcome::Dropped(reason) => write!(f, "dropped: {}", reason),
+            TxOutcome::Simulated(Ok(_)) => write!(f, "simulated successfully (dry run)"),
+            TxOutcome::Simulated(Err(reason)) => {
+                write!(f, "simulated with revert reason {} (dry run)", reason)
+            }
+        }
+    }
+}
+
+/// The [TxRevert] enum describes why a transaction reverted, decoded with the ABIs of the
+/// `FaultDisputeGame` and `DisputeGameFactory` contracts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxRevert {
+    /// A custom error of the `FaultDisputeGame` contract.
+    Game(GameError),
+    /// The `DisputeGameFactory` already holds a game with the same UUID.
+    GameAlreadyExists(H256),
+    /// The `DisputeGameFactory` has no implementation for the game type.
+    NoImplementation(u8),
+    /// An `Error(string)` revert.
+    Reason(String),
+    /// Revert data that matches none of the known errors.
+    Unknown(Bytes),
+}
+
+/// The [RevertAction] enum defines how the dispatcher of a transaction reacts to a [TxRevert].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevertAction {
+    /// The transaction is no longer needed, and is not retried.
+    Drop,
+    /// The local state of the game is out of date. The game is re-synced, which only plans
+    /// responses to claims that are new to the local state. The reverted transaction is not
+    /// retried, since the same response would revert again.
+    Resync,
+    /// The transaction can never succeed, which points at a bug in the challenger or its
+    /// configuration. An error is raised, and the transaction is not retried.
+    Alert,
+}
+
+impl TxRevert {
+    /// Decodes the given revert data.
+    pub fn decode(data: &[u8]) -> Self {
+        use crate::bindings::{
+            dispute_game_factory::DisputeGame_FactoryErrors as FactoryErrors,
+            fault_dispute_game::FaultDisputeGameErrors as GameErrors,
+        };
+
+        /// The selector of `Error(string)`.
+        const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+        // The generated error enums only decode the custom errors of their contract.
+        if let Some((selector, message)) = data.split_first_chunk::<4>() {
+            if *selector == ERROR_SELECTOR {
+                if let Ok(reason) = String::decode(message) {
+                    return TxRevert::Reason(reason);
+                }
+            }
+        }
+        if let Ok(error) = GameErrors::decode(data) {
+            return match error {
+                GameErrors::GameNotInProgress(_) => TxRevert::Game(GameError::GameNotInProgress),
+                GameErrors::ClockTimeExceeded(_) => TxRevert::Game(GameError::ClockTimeExceeded),
+                GameErrors::ClockNotExpired(_) => TxRevert::Game(GameError::ClockNotExpired),
+                GameErrors::CannotDefendRootClaim(_) => {
+                    TxRevert::Game(GameError::CannotDefendRootClaim)
+                }
+                GameErrors::ClaimAlreadyExists(_) => TxRevert::Game(GameError::ClaimAlreadyExists),
+                GameErrors::GameDepthExceeded(_) => TxRevert::Game(GameError::GameDepthExceeded),
+                GameErrors::InvalidParent(_) => TxRevert::Game(GameError::InvalidParent),
+                GameErrors::InvalidPrestate(_) => TxRevert::Game(GameError::InvalidPrestate),
+                GameErrors::ValidStep(_) => TxRevert::Game(GameError::ValidStep),
+                GameErrors::RevertString(reason) => TxRevert::Reason(reason),
+            };
+        }
+        match FactoryErrors::decode(data) {
+            Ok(FactoryErrors::GameAlreadyExists(error)) => {
+                TxRevert::GameAlreadyExists(H256(error.uuid))
+            }
+            Ok(FactoryErrors::NoImplementation(error)) => {
+                TxRevert::NoImplementation(error.game_type)
+            }
+            Ok(FactoryErrors::RevertString(reason)) => TxRevert::Reason(reason),
+            Err(_) => TxRevert::Unknown(Bytes::from(data.to_vec())),
+        }
+    }
+
+    /// Returns the [RevertAction] that the dispatcher of the transaction takes.
+    pub fn action(&self) -> RevertAction {
+        match self {
+            // The game was resolved, the clock ran out or the game was created by someone else;
+            // there is nothing left to do.
+            TxRevert::Game(
+                GameError::GameNotInProgress
+                | GameError::ClockTimeExceeded
+                | GameError::ClockNotExpired,
+            )
+            | TxRevert::GameAlreadyExists(_) => RevertAction::Drop,
+            // The claims of the game have changed since the response was planned.
+            TxRevert::Game(GameError::ClaimAlreadyExists | GameError::InvalidParent) => {
+                RevertAction::Resync
+            }
+            TxRevert::Game(
+                GameError::CannotDefendRootClaim
+                | GameError::GameDepthExceeded
+                | GameError::InvalidPrestate
+                | GameError::ValidStep,
+            )
+            | TxRevert::NoImplementation(_)
+            | TxRevert::Reason(_)
+            | TxRevert::Unknown(_) => RevertAction::Alert,
+        }
+    }
+}
+
+impl std::fmt::Display for TxRevert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TxRevert::Game(error) => write!(f, "{}", error),
+            TxRevert::GameAlreadyExists(uuid) => write!(f, "GameAlreadyExists({:?})", uuid),
+            TxRevert::NoImplementation(game_type) => {
+                write!(f, "NoImplementation({})", game_type)
+            }
+            TxRevert::Reason(reason) => write!(f, "{}", reason),
+            TxRevert::Unknown(data) if data.is_empty() => write!(f, "no revert data"),
+            TxRevert::Unknown(data) => write!(f, "{}", data),
+        }
+    }
+}
+
+/// The [TxKind] enum defines the purposes of the transactions dispatched through the
+/// [TxDispatchDriver](crate::TxDispatchDriver).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum TxKind {
+    /// An `attack` or `defend` move in a fault dispute game.
+    Move,
+    /// A `step` against a claim at the maximum depth of a fault dispute game.
+    Step,
+    /// The resolution of a dispute game.
+    Resolve,
+    /// The creation of a dispute game.
+    CreateGame,
+}
+
+impl TxKind {
+    /// Returns the default priority of transactions of this kind. Transactions that counter a
+    /// claim before its clock runs out are the most urgent.
+    pub fn priority(&self) -> u8 {
+        match self {
+            TxKind::Step => 3,
+            TxKind::Move => 2,
+            TxKind::CreateGame => 1,
+            TxKind::Resolve => 0,
+        }
+    }
+}
+
+/// The [TxMeta] struct holds the metadata that the [TxQueue](crate::TxQueue) orders dispatched
+/// transactions by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxMeta {
+    /// The address of the game that the transaction acts on, if any.
+    pub game: Option<Address>,
+    /// The purpose of the transaction.
+    pub kind: TxKind,
+    /// The UNIX timestamp after which the transaction is useless, if any. Transactions are
+    /// dispatched in order of their deadline, and dropped once it has passed.
+    pub deadline: Option<u64>,
+    /// The priority of the transaction among transactions with the same deadline. Higher is
+    /// more urgent.
+    pub priority: u8,
+}
+
+impl TxMeta {
+    /// Creates a new [TxMeta] of the given kind, with the kind's default priority and no
+    /// deadline.
+    pub fn new(kind: TxKind) -> Self {
+        Self {
+            game: None,
+            kind,
+            deadline: None,
+            priority: kind.priority(),
+        }
+    }
+
+    /// Sets the address of the game that the transaction acts on.
+    pub fn game(mut self, game: Address) -> Self {
+        self.game = Some(game);
+        self
+    }
+
+    /// Sets the UNIX timestamp after which the transaction is useless.
+    pub fn deadline(mut self, deadline: u64) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+}
+
+/// The [TxRequest] struct is a request to the [TxDispatchDriver](crate::TxDispatchDriver) to
+/// send a transaction. The [TxOutcome] of the transaction is sent back once it is known.
+#[derive(Debug)]
+pub struct TxRequest {
+    /// The transaction to send. Only its recipient, calldata and value are used.
+    pub tx: TypedTransaction,
+    /// The metadata that the request is ordered by.
+    pub meta: TxMeta,
+    /// The sending handle of the channel that the [TxOutcome] is sent to. The receiving handle
+    /// may be dropped if the outcome is not needed.
+    pub outcome: oneshot::Sender<TxOutcome>,
+}
+
+impl TxRequest {
+    /// Creates a new [TxRequest] for the given transaction, along with the receiving handle of
+    /// its [TxOutcome].
+    pub fn new(tx: TypedTransaction, meta: TxMeta) -> (Self, oneshot::Receiver<TxOutcome>) {
+        let (outcome, receiver) = oneshot::channel();
+        (Self { tx, meta, outcome }, receiver)
+    }
+}
+
+/// The [GasBumpPolicy] struct defines when the fees of a stuck transaction are bumped, and by
+/// how much.
+#[derive(Debug, Clone, Copy)]
+pub struct GasBumpPolicy {
+    /// The time after which a pending transaction is considered stuck and is replaced.
+    pub bump_after: Duration,
+    /// The percentage that both fees are increased by on each replacement. Nodes only accept
+    /// replacements that increase both fees by at least 10%.
+    pub bump_percent: u64,
+    /// The maximum fee per gas, in wei, that a transaction is ever sent with.
+    pub max_fee_per_gas: U256,
+}
+
+impl Default for GasBumpPolicy {
+    fn default() -> Self {
+        Self {
+            bump_after: Duration::from_secs(36),
+            bump_percent: 20,
+            max_fee_per_gas: U256::from(500) * U256::exp10(9),
+        }
+    }
+}
+
+impl GasBumpPolicy {
+    /// Returns the given fee, increased by [GasBumpPolicy::bump_percent]. Rounds up, so that
+    /// small fees are always increased. Saturates at [U256::MAX] instead of overflowing; the
+    /// result is clamped to [GasBumpPolicy::max_fee_per_gas] by the caller.
+    pub fn bump(&self, fee: U256) -> U256 {
+        fee.checked_mul(U256::from(100) + self.bump_percent)
+            .and_then(|bumped| bumped.checked_add(U256::from(99)))
+            .map_or(U256::MAX, |bumped| bumped / 100)
+    }
+}
+
+/// The [ClaimDataLayout] enum defines the layouts of the `ClaimData` struct returned by the
+/// `claimData` getter of the `FaultDisputeGame` contract, across its versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClaimDataLayout {
+    /// `(uint32 parentIndex, bool countered, bytes32 claim, uint128 position, uint128 clock)`
+    V0,
+    /// `(uint32 parentIndex, address counteredBy, address claimant, uint128 bond, bytes32 claim,
+    /// uint128 position, uint128 clock)`
+    V1,
+}
+
+impl ClaimDataLayout {
+    /// The first `FaultDisputeGame` version using the [ClaimDataLayout::V1] layout.
+    const V1_VERSION: (u64, u64, u64) = (0, 8, 0);
+
+    /// Returns the [ClaimDataLayout] of a `FaultDisputeGame` contract from the semantic version
+    /// reported by its `version()` getter.
+    pub fn from_version(version: &str) -> anyhow::Result<Self> {
+        // Ignore any pre-release or build metadata, e.g. `0.8.0-beta.1`.
+        let core = version.split(['-', '+']).next().unwrap_or_default();
+        let parts = core
+            .split('.')
+            .map(|part| part.parse::<u64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| anyhow::anyhow!("Invalid FaultDisputeGame version {}", version))?;
+        let [major, minor, patch] = parts[..] else {
+            anyhow::bail!("Invalid FaultDisputeGame version {}", version);
+        };
+
+        if (major, minor, patch) >= Self::V1_VERSION {
+            Ok(ClaimDataLayout::V1)
+        } else {
+            Ok(ClaimDataLayout::V0)
+        }
+    }
+}
+
 /// The [SignerMiddlewareWS] type is a [SignerMiddleware] that uses a [Provider] with a [Ws] transport.
 pub(crate) type SignerMiddlewareWS = SignerMiddleware<Provider<Ws>, LocalWallet>;
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub(crate) struct OutputAtBlockResponse {
-    pub output_root: H256,
+#[cfg(test)]
+mod test {
+    use super::{
+        ClaimDataLayout, ConfirmationDepth, GameType, GasBumpPolicy, RevertAction, TxRevert,
+    };
+    use crate::bindings::{dispute_game_factory, fault_dispute_game};
+    use ethers::{
+        abi::AbiEncode,
+        types::{BlockNumber, Bytes, H256, U256},
+        utils::hex,
+    };
+    use op_challenger_solvers::fault::GameError;
+
+    #[test]
+    fn game_type_from_str() {
+        assert_eq!("fault".parse::<GameType>().unwrap(), GameType::Fault);
+        assert_eq!(
+            "output-attestation".parse::<GameType>().unwrap(),
+            GameType::OutputAttestation
+        );
+        assert_eq!("1".parse::<GameType>().unwrap(), GameType::Validity);
+        assert!("3".parse::<GameType>().is_err());
+        assert!("cannon".parse::<GameType>().is_err());
+    }
+
+    #[test]
+    fn confirmation_depth_from_str() {
+        assert_eq!(
+            "latest".parse::<ConfirmationDepth>().unwrap(),
+            ConfirmationDepth::Blocks(0)
+        );
+        assert_eq!(
+            "safe".parse::<ConfirmationDepth>().unwrap(),
+            ConfirmationDepth::Safe
+        );
+        assert_eq!(
+            "finalized".parse::<ConfirmationDepth>().unwrap(),
+            ConfirmationDepth::Finalized
+        );
+        assert_eq!(
+            "12".parse::<ConfirmationDepth>().unwrap(),
+            ConfirmationDepth::Blocks(12)
+        );
+        assert!("-1".parse::<ConfirmationDepth>().is_err());
+        assert!("unsafe".parse::<ConfirmationDepth>().is_err());
+    }
+
+    #[test]
+    fn confirmed_blocks() {
+        assert_eq!(
+            ConfirmationDepth::Blocks(10).confirmed_block(Some(100)),
+            BlockNumber::Number(90.into())
+        );
+        assert_eq!(
+            ConfirmationDepth::Blocks(0).confirmed_block(Some(100)),
+            BlockNumber::Number(100.into())
+        );
+        // Depths beyond genesis are clamped to it.
+        assert_eq!(
+            ConfirmationDepth::Blocks(200).confirmed_block(Some(100)),
+            BlockNumber::Number(0.into())
+        );
+        assert_eq!(
+            ConfirmationDepth::Blocks(0).confirmed_block(None),
+            BlockNumber::Latest
+        );
+        assert_eq!(
+            ConfirmationDepth::Safe.confirmed_block(Some(100)),
+            BlockNumber::Safe
+        );
+        assert_eq!(
+            ConfirmationDepth::Finalized.confirmed_block(None),
+            BlockNumber::Finalized
+        );
+    }
+
+    #[test]
+    fn decodes_reverts() {
+        let mut data = hex::decode("08c379a0").unwrap();
+        data.extend("Game not in progress".to_string().encode());
+        assert_eq!(
+            TxRevert::decode(&data),
+            TxRevert::Reason("Game not in progress".to_string())
+        );
+
+        let revert = TxRevert::decode(&fault_dispute_game::ClaimAlreadyExists.encode());
+        assert_eq!(revert, TxRevert::Game(GameError::ClaimAlreadyExists));
+        assert_eq!(revert.action(), RevertAction::Resync);
+
+        let revert = TxRevert::decode(
+            &dispute_game_factory::GameAlreadyExists { uuid: [0xaa; 32] }.encode(),
+        );
+        assert_eq!(revert, TxRevert::GameAlreadyExists(H256::repeat_byte(0xaa)));
+        assert_eq!(revert.action(), RevertAction::Drop);
+
+        let revert = TxRevert::decode(&[0x12, 0x34, 0x56, 0x78]);
+        assert_eq!(
+            revert,
+            TxRevert::Unknown(Bytes::from(vec![0x12, 0x34, 0x56, 0x78]))
+        );
+        assert_eq!(revert.to_string(), "0x12345678");
+        assert_eq!(revert.action(), RevertAction::Alert);
+        assert_eq!(TxRevert::decode(&[]).to_string(), "no revert data");
+    }
+
+    #[test]
+    fn gas_bump_policy_bumps_fees() {
+        let policy = GasBumpPolicy::default();
+        assert_eq!(policy.bump(U256::from(100)), U256::from(120));
+        // Rounds up, so that a replacement is always accepted.
+        assert_eq!(policy.bump(U256::from(1)), U256::from(2));
+        assert_eq!(policy.bump(U256::zero()), U256::zero());
+        // Never overflows.
+        assert_eq!(policy.bump(U256::MAX / 2), U256::MAX);
+        let policy = GasBumpPolicy {
+            bump_percent: u64::MAX,
+            ..GasBumpPolicy::default()
+        };
+        assert_eq!(policy.bump(U256::from(100)), U256::from(100) + u64::MAX);
+    }
+
+    #[test]
+    fn claim_data_layout_from_version() {
+        assert_eq!(
+            ClaimDataLayout::from_version("0.0.4").unwrap(),
+            ClaimDataLayout::V0
+        );
+        assert_eq!(
+            ClaimDataLayout::from_version("0.7.12").unwrap(),
+            ClaimDataLayout::V0
+        );
+        assert_eq!(
+            ClaimDataLayout::from_version("0.8.0-beta.1").unwrap(),
+            ClaimDataLayout::V1
+        );
+        assert_eq!(
+            ClaimDataLayout::from_version("1.2.0").unwrap(),
+            ClaimDataLayout::V1
+        );
+        assert!(ClaimDataLayout::from_version("1.2").is_err());
+        assert!(ClaimDataLayout::from_version("v1.2.0").is_err());
+    }
 }
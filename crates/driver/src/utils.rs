@@ -1,10 +1,19 @@
-use crate::types::OutputAtBlockResponse;
-use anyhow::Result;
+use crate::{ConfirmationDepth, DriverConfig, GlobalState, L1BlockRef, SignerMiddlewareWS};
+use anyhow::{anyhow, Result};
 use ethers::{
-    providers::{Http, Provider},
-    types::H256,
+    providers::{Http, Middleware, Provider, StreamExt},
+    types::{Address, Filter, Log, H256},
 };
-use std::sync::Arc;
+use op_challenger_solvers::fault::providers::OutputAtBlock;
+use std::{future::Future, ops::RangeInclusive, sync::Arc, time::Duration};
+use tokio::sync::Mutex;
+
+/// The maximum number of blocks fetched in a single `eth_getLogs` request when backfilling events.
+const LOG_PAGE_SIZE: u64 = 1_000;
+
+/// The interval at which the confirmation depth of a pending event is checked. This is the L1
+/// block time.
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_secs(12);
 
 /// Compares the untrusted output root against the trusted output root from the node at a given
 /// block number.
@@ -16,13 +25,13 @@ use std::sync::Arc;
 ///
 /// # Returns
 /// * Some(bool) if the request was successful, None otherwise. The bool represents whether the
-/// untrusted root matches the trusted root.
+///   untrusted root matches the trusted root.
 pub(crate) async fn compare_output_root(
     node_provider: Arc<Provider<Http>>,
     untrusted_root: &H256,
     block_number: u64,
-) -> Result<(bool, OutputAtBlockResponse)> {
-    let trusted_root: OutputAtBlockResponse = node_provider
+) -> Result<(bool, OutputAtBlock)> {
+    let trusted_root: OutputAtBlock = node_provider
         .request(
             "optimism_outputAtBlock",
             vec![format!("0x{:x}", block_number)],
@@ -31,3 +40,357 @@ pub(crate) async fn compare_output_root(
 
     Ok((untrusted_root == &trusted_root.output_root, trusted_root))
 }
+
+/// Selects the cursor in the [GlobalState] that tracks the last L1 block whose events of a kind
+/// have been processed.
+pub(crate) type EventCursor = fn(&mut GlobalState) -> &mut Option<u64>;
+
+/// Processes every log matching the given filter, without gaps or duplicates. The logs emitted
+/// since the cursor, or since the configured start block, are backfilled with paged
+/// `eth_getLogs` requests, before new logs are processed as they arrive over a subscription.
+/// The cursor is advanced and persisted as logs are processed.
+///
+/// # Takes
+/// * `config` - The driver configuration.
+/// * `state` - The global state that holds the cursor.
+/// * `event` - The name of the event, for logging.
+/// * `filter` - The filter of the logs to process.
+/// * `cursor` - The cursor of the event.
+/// * `handle` - The handler of each log. Removed logs are passed to it as well. An error stops
+///   the processing of logs.
+///
+/// # Returns
+/// * Ok(()) once the subscription is closed, or the first error of the handler or the provider.
+pub(crate) async fn index_events<F, Fut>(
+    config: &DriverConfig,
+    state: &Mutex<GlobalState>,
+    event: &str,
+    filter: &Filter,
+    cursor: EventCursor,
+    mut handle: F,
+) -> Result<()>
+where
+    F: FnMut(Log) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    // Subscribe before backfilling, so that no events are missed between the end of the
+    // backfill and the start of the subscription.
+    let mut stream = config.l1_provider.subscribe_logs(filter).await?;
+    let head = config.l1_provider.get_block_number().await?.as_u64();
+
+    // Resume from the persisted cursor, unless the configured start block is ahead of it.
+    let from = backfill_start(*cursor(&mut *state.lock().await), config.start_block);
+    if let Some(from) = from {
+        tracing::info!(target: "event-indexer", "Backfilling {} events from block {} to block {}...", event, from, head);
+        for page in log_pages(from, head, LOG_PAGE_SIZE) {
+            let logs = config
+                .l1_provider
+                .get_logs(
+                    &filter
+                        .clone()
+                        .from_block(*page.start())
+                        .to_block(*page.end()),
+                )
+                .await?;
+            tracing::debug!(target: "event-indexer", "Fetched {} {} events from blocks {:?}", logs.len(), event, page);
+
+            for log in logs {
+                handle(log).await?;
+            }
+            persist_cursor(config, state, cursor, Some(*page.end())).await;
+        }
+        tracing::info!(target: "event-indexer", "Backfill of {} events complete.", event);
+    }
+
+    tracing::info!(target: "event-indexer", "Subscribed to {} events, beginning event loop.", event);
+    while let Some(log) = stream.next().await {
+        // Removals are handled before anything else, since the logs they remove may already
+        // have been processed.
+        let block_number = log.block_number.map(|n| n.as_u64());
+        if log.removed == Some(true) {
+            handle(log).await?;
+            continue;
+        }
+
+        // Logs in blocks that the cursor has passed have already been processed by the
+        // backfill. Handlers that roll the cursor back on a removal make sure that the logs
+        // replacing the removed ones are not skipped.
+        let processed = *cursor(&mut *state.lock().await);
+        if block_number.is_some_and(|block| Some(block) <= processed) {
+            continue;
+        }
+
+        // Other logs may still be emitted in the same block, so the cursor only advances to the
+        // block before this log.
+        handle(log).await?;
+        persist_cursor(
+            config,
+            state,
+            cursor,
+            block_number.and_then(|block| block.checked_sub(1)),
+        )
+        .await;
+    }
+    Ok(())
+}
+
+/// Advances the given cursor to the given L1 block and persists the global state, if the block
+/// is ahead of the cursor.
+async fn persist_cursor(
+    config: &DriverConfig,
+    state: &Mutex<GlobalState>,
+    cursor: EventCursor,
+    block: Option<u64>,
+) {
+    let mut state = state.lock().await;
+    if !advance_cursor(cursor(&mut state), block) {
+        return;
+    }
+    if let Err(e) = config.store.save(&state) {
+        tracing::error!(target: "event-indexer", "Failed to persist global state: {}", e);
+    }
+}
+
+/// Returns the first L1 block to backfill events from. A persisted cursor is resumed from, unless
+/// the configured start block is ahead of it.
+///
+/// # Takes
+/// * `cursor` - The last L1 block whose events have been processed, if any.
+/// * `start_block` - The configured start block, if any.
+///
+/// # Returns
+/// * The first block to backfill, or None if there is nothing to backfill from.
+pub(crate) fn backfill_start(cursor: Option<u64>, start_block: Option<u64>) -> Option<u64> {
+    cursor.map(|block| block.saturating_add(1)).max(start_block)
+}
+
+/// Splits the block range `[from, to]` into consecutive pages of at most `page_size` blocks, so
+/// that the range can be fetched with `eth_getLogs` without gaps or overlaps.
+///
+/// # Takes
+/// * `from` - The first block of the range.
+/// * `to` - The last block of the range.
+/// * `page_size` - The maximum number of blocks in a page.
+///
+/// # Returns
+/// * The pages of the range, in order. Empty if `from` is greater than `to`.
+pub(crate) fn log_pages(
+    from: u64,
+    to: u64,
+    page_size: u64,
+) -> impl Iterator<Item = RangeInclusive<u64>> {
+    let mut next = (from <= to).then_some(from);
+    std::iter::from_fn(move || {
+        let start = next?;
+        let end = start.saturating_add(page_size.max(1) - 1).min(to);
+        next = (end < to).then(|| end + 1);
+        Some(start..=end)
+    })
+}
+
+/// Advances an event cursor to the given L1 block, if it is ahead of the cursor.
+///
+/// # Takes
+/// * `cursor` - The last L1 block whose events have been processed.
+/// * `block` - The block to advance the cursor to.
+///
+/// # Returns
+/// * true if the cursor was advanced.
+pub(crate) fn advance_cursor(cursor: &mut Option<u64>, block: Option<u64>) -> bool {
+    if block <= *cursor {
+        return false;
+    }
+    *cursor = block;
+    true
+}
+
+/// Fetches a reference to the latest L1 block that satisfies the given [ConfirmationDepth].
+///
+/// # Takes
+/// * `l1_provider` - The L1 provider.
+/// * `depth` - The confirmation depth.
+///
+/// # Returns
+/// * The number and hash of the latest confirmed L1 block.
+pub(crate) async fn confirmed_l1_block(
+    l1_provider: &SignerMiddlewareWS,
+    depth: ConfirmationDepth,
+) -> Result<L1BlockRef> {
+    // The latest block number is only needed for depths in blocks beyond the latest block.
+    let latest = match depth {
+        ConfirmationDepth::Blocks(blocks) if blocks > 0 => {
+            Some(l1_provider.get_block_number().await?.as_u64())
+        }
+        _ => None,
+    };
+    let block_number = depth.confirmed_block(latest);
+    let block = l1_provider
+        .get_block(block_number)
+        .await?
+        .ok_or(anyhow!("L1 block {:?} not found", block_number))?;
+    Ok(L1BlockRef {
+        number: block
+            .number
+            .ok_or(anyhow!("L1 block {:?} has no number", block_number))?
+            .as_u64(),
+        hash: block
+            .hash
+            .ok_or(anyhow!("L1 block {:?} has no hash", block_number))?,
+    })
+}
+
+/// Waits until the block that the given [Log] was emitted in satisfies the given
+/// [ConfirmationDepth].
+///
+/// # Takes
+/// * `l1_provider` - The L1 provider.
+/// * `depth` - The confirmation depth.
+/// * `log` - The log to wait for.
+///
+/// # Returns
+/// * true if the log is confirmed, false if its block was reorged out while waiting.
+pub(crate) async fn wait_for_confirmation(
+    l1_provider: &SignerMiddlewareWS,
+    depth: ConfirmationDepth,
+    log: &Log,
+) -> Result<bool> {
+    if depth == ConfirmationDepth::Blocks(0) {
+        return Ok(true);
+    }
+    let block = L1BlockRef::from_log(log).ok_or(anyhow!("Log has not been included in a block"))?;
+
+    while confirmed_l1_block(l1_provider, depth).await?.number < block.number {
+        tokio::time::sleep(CONFIRMATION_POLL_INTERVAL).await;
+    }
+    is_canonical(l1_provider, &block).await
+}
+
+/// Checks whether the given block is still part of the canonical L1 chain.
+///
+/// # Takes
+/// * `l1_provider` - The L1 provider.
+/// * `block` - The block to check.
+///
+/// # Returns
+/// * true if the canonical block at the given block's number has the same hash.
+pub(crate) async fn is_canonical(
+    l1_provider: &SignerMiddlewareWS,
+    block: &L1BlockRef,
+) -> Result<bool> {
+    Ok(l1_provider
+        .get_block(block.number)
+        .await?
+        .and_then(|canonical| canonical.hash)
+        == Some(block.hash))
+}
+
+/// Stops tracking every game whose `DisputeGameCreated` event is no longer part of the canonical
+/// L1 chain.
+///
+/// # Takes
+/// * `l1_provider` - The L1 provider.
+/// * `state` - The global state to remove the games from. It is not locked while the origins
+///   of the games are checked.
+///
+/// # Returns
+/// * The addresses of the removed games.
+pub(crate) async fn remove_reorged_games(
+    l1_provider: &SignerMiddlewareWS,
+    state: &Mutex<GlobalState>,
+) -> Result<Vec<Address>> {
+    // Check the origins without holding the lock on the global state.
+    let origins = state.lock().await.game_origins.clone();
+    let mut reorged = Vec::new();
+    for (address, origin) in origins {
+        if !is_canonical(l1_provider, &origin).await? {
+            reorged.push((address, origin));
+        }
+    }
+
+    // The origin of a game may have been updated in the meantime.
+    let mut state = state.lock().await;
+    Ok(reorged
+        .into_iter()
+        .filter_map(|(address, origin)| {
+            (state.game_origins.get(&address) == Some(&origin) && state.remove_game(address))
+                .then_some(address)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{advance_cursor, backfill_start, log_pages};
+    use crate::{GlobalState, MemoryStore, SledStore, StateStore};
+
+    #[test]
+    fn pages_block_ranges() {
+        assert_eq!(
+            log_pages(0, 2_499, 1_000).collect::<Vec<_>>(),
+            vec![0..=999, 1_000..=1_999, 2_000..=2_499]
+        );
+        // Ranges that end on a page boundary are not followed by an empty page.
+        assert_eq!(
+            log_pages(1, 2_000, 1_000).collect::<Vec<_>>(),
+            vec![1..=1_000, 1_001..=2_000]
+        );
+        assert_eq!(log_pages(5, 5, 1_000).collect::<Vec<_>>(), vec![5..=5]);
+        assert_eq!(log_pages(6, 5, 1_000).count(), 0);
+        assert_eq!(
+            log_pages(u64::MAX - 1, u64::MAX, 1_000).collect::<Vec<_>>(),
+            vec![u64::MAX - 1..=u64::MAX]
+        );
+    }
+
+    #[test]
+    fn selects_backfill_start() {
+        assert_eq!(backfill_start(None, None), None);
+        assert_eq!(backfill_start(None, Some(100)), Some(100));
+        assert_eq!(backfill_start(Some(99), None), Some(100));
+        // The start block takes priority over a cursor that is behind it, and the cursor over
+        // a start block that it has already passed.
+        assert_eq!(backfill_start(Some(49), Some(100)), Some(100));
+        assert_eq!(backfill_start(Some(149), Some(100)), Some(150));
+    }
+
+    #[test]
+    fn resumes_from_persisted_cursor() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SledStore::open(dir.path()).unwrap();
+        let mut state = GlobalState::default();
+        for block in [Some(1_999), Some(999), None] {
+            advance_cursor(&mut state.last_l1_block, block);
+        }
+        assert_eq!(state.last_l1_block, Some(1_999));
+        store.save(&state).unwrap();
+        drop(store);
+
+        let state = SledStore::open(dir.path())
+            .unwrap()
+            .load()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            backfill_start(state.last_l1_block, Some(1_000)),
+            Some(2_000)
+        );
+
+        // Without a persisted state, the backfill starts from the start block.
+        let state = MemoryStore.load().unwrap().unwrap_or_default();
+        assert_eq!(
+            backfill_start(state.last_l1_block, Some(1_000)),
+            Some(1_000)
+        );
+    }
+
+    #[test]
+    fn advances_cursor_forwards_only() {
+        let mut cursor = None;
+        assert!(!advance_cursor(&mut cursor, None));
+        assert!(advance_cursor(&mut cursor, Some(10)));
+        assert!(!advance_cursor(&mut cursor, Some(10)));
+        assert!(!advance_cursor(&mut cursor, Some(9)));
+        assert!(advance_cursor(&mut cursor, Some(11)));
+        assert_eq!(cursor, Some(11));
+    }
+}
@@ -0,0 +1,449 @@
+//! The emulator module contains the [FaultGameEmulator], an in-process emulation of the
+//! `FaultDisputeGame` contract played over the alphabet VM. It allows solvers to play full games
+//! against each other without a devnet.
+
+use super::{Claim, ClaimData, Clock, GameError, GameStatus, Position, Resolution};
+use ethers::{
+    abi::{self, ParamType, Token},
+    types::{Bytes, U256},
+    utils::keccak256,
+};
+use std::collections::HashSet;
+
+/// The parent index of the root claim.
+const ROOT_PARENT_INDEX: usize = u32::MAX as usize;
+
+/// The [FaultGameEmulator] emulates the `attack`, `defend`, `step` and `resolve` semantics of the
+/// `FaultDisputeGame` contract, including its claim DAG, chess clocks, and step validation for
+/// the alphabet VM. Time only advances when [FaultGameEmulator::warp] is called.
+#[derive(Debug, Clone)]
+pub struct FaultGameEmulator {
+    /// The maximum depth of the game tree.
+    pub max_depth: u64,
+    /// The absolute prestate of the alphabet VM.
+    pub absolute_prestate: Claim,
+    /// The total duration of the game's chess clocks, in seconds.
+    pub game_duration: u64,
+    /// The UNIX timestamp of the game's creation.
+    pub created_at: u64,
+    /// The current UNIX timestamp of the emulated chain.
+    pub timestamp: u64,
+    /// The status of the game.
+    pub status: GameStatus,
+    /// The claim DAG.
+    pub claim_data: Vec<ClaimData>,
+    /// The set of `(claim, position)` pairs that have been made, used to reject duplicates.
+    claims: HashSet<(Claim, u128)>,
+}
+
+impl FaultGameEmulator {
+    /// Creates a new [FaultGameEmulator] with the given root claim.
+    ///
+    /// ### Takes
+    /// - `root_claim`: The root claim of the game.
+    /// - `max_depth`: The maximum depth of the game tree.
+    /// - `absolute_prestate`: The absolute prestate of the alphabet VM.
+    /// - `game_duration`: The total duration of the game's chess clocks, in seconds.
+    /// - `created_at`: The UNIX timestamp of the game's creation.
+    pub fn new(
+        root_claim: Claim,
+        max_depth: u64,
+        absolute_prestate: Claim,
+        game_duration: u64,
+        created_at: u64,
+    ) -> Self {
+        Self {
+            max_depth,
+            absolute_prestate,
+            game_duration,
+            created_at,
+            timestamp: created_at,
+            status: GameStatus::InProgress,
+            claim_data: vec![ClaimData {
+                parent_index: ROOT_PARENT_INDEX,
+                countered: false,
+                claim: root_claim,
+                position: 1,
+                clock: Clock {
+                    duration: 0,
+                    timestamp: created_at,
+                },
+            }],
+            claims: HashSet::from([(root_claim, 1)]),
+        }
+    }
+
+    /// Advances the emulated chain's timestamp by the given number of seconds.
+    pub fn warp(&mut self, seconds: u64) {
+        self.timestamp += seconds;
+    }
+
+    /// Attacks the claim at the given index with the given pivot claim.
+    pub fn attack(&mut self, parent_index: usize, pivot: Claim) -> Result<(), GameError> {
+        self.make_move(parent_index, pivot, true)
+    }
+
+    /// Defends the claim at the given index with the given pivot claim.
+    pub fn defend(&mut self, parent_index: usize, pivot: Claim) -> Result<(), GameError> {
+        self.make_move(parent_index, pivot, false)
+    }
+
+    /// Performs a step of the alphabet VM against the claim at `claim_index`, which must be at the
+    /// maximum depth of the game. If the step shows that the claim commits to an invalid state,
+    /// the claim is countered.
+    ///
+    /// ### Takes
+    /// - `state_index`: The index of the claim that commits to the prestate (attack) or the post
+    ///   state (defense) of the step. Ignored when stepping from the absolute prestate.
+    /// - `claim_index`: The index of the claim being stepped against.
+    /// - `is_attack`: Whether the step is an attack or a defense.
+    /// - `state_data`: The preimage of the prestate claim.
+    /// - `_proof`: The step proof, unused by the alphabet VM.
+    pub fn step(
+        &mut self,
+        state_index: usize,
+        claim_index: usize,
+        is_attack: bool,
+        state_data: &Bytes,
+        _proof: &Bytes,
+    ) -> Result<(), GameError> {
+        self.assert_in_progress()?;
+
+        let parent = self
+            .claim_data
+            .get(claim_index)
+            .ok_or(GameError::InvalidParent)?;
+        if parent.position.depth() != self.max_depth {
+            return Err(GameError::InvalidParent);
+        }
+        if self.next_duration(parent) > self.game_duration >> 1 {
+            return Err(GameError::ClockTimeExceeded);
+        }
+
+        // Determine the claims that commit to the pre and post states of the step. The state claim
+        // must be an ancestor of the parent that commits to the trace index adjacent to the
+        // parent's.
+        let step_pos = parent.position.make_move(is_attack);
+        let (pre_claim, post_claim, post_depth) = if is_attack && step_pos.index_at_depth() == 0 {
+            (None, parent.claim, parent.position.depth())
+        } else {
+            let state = self
+                .claim_data
+                .get(state_index)
+                .ok_or(GameError::InvalidPrestate)?;
+            let expected_pos = if is_attack {
+                parent.position - 1
+            } else {
+                parent.position + 1
+            };
+            if state.position.right_index(self.max_depth) != expected_pos
+                || !self.is_ancestor(state_index, claim_index)
+            {
+                return Err(GameError::InvalidPrestate);
+            }
+
+            if is_attack {
+                (Some(state.claim), parent.claim, parent.position.depth())
+            } else {
+                (Some(parent.claim), state.claim, state.position.depth())
+            }
+        };
+
+        // If the parent and the post state claim were made by the same team, the parent is
+        // countered if the step does not reproduce the post state. Otherwise, the parent is only
+        // countered if the step reproduces the post state that the parent disagrees with.
+        let parent_post_agree = (parent.position.depth() - post_depth) % 2 == 0;
+        let valid_step = self.alphabet_step(pre_claim, state_data)? == post_claim;
+        if parent_post_agree == valid_step {
+            return Err(GameError::ValidStep);
+        }
+        self.claim_data[claim_index].countered = true;
+        Ok(())
+    }
+
+    /// Resolves the game once all chess clocks have run out.
+    ///
+    /// ### Returns
+    /// - `Ok(GameStatus)`: The resolved status of the game.
+    /// - `Err(GameError)`: The game is not in progress, or a clock has not yet expired.
+    pub fn resolve(&mut self) -> Result<GameStatus, GameError> {
+        self.assert_in_progress()?;
+        let resolution = Resolution::new(&self.claim_data, self.max_depth, self.game_duration)
+            .map_err(|_| GameError::InvalidParent)?;
+        if !resolution.is_resolvable(self.timestamp) {
+            return Err(GameError::ClockNotExpired);
+        }
+
+        self.status = resolution.status;
+        Ok(self.status)
+    }
+
+    /// Makes a move against the claim at `challenge_index`.
+    fn make_move(
+        &mut self,
+        challenge_index: usize,
+        pivot: Claim,
+        is_attack: bool,
+    ) -> Result<(), GameError> {
+        self.assert_in_progress()?;
+        if !is_attack && challenge_index == 0 {
+            return Err(GameError::CannotDefendRootClaim);
+        }
+
+        let parent = self
+            .claim_data
+            .get(challenge_index)
+            .ok_or(GameError::InvalidParent)?;
+        let next_position = parent.position.make_move(is_attack);
+        if next_position.depth() > self.max_depth {
+            return Err(GameError::GameDepthExceeded);
+        }
+        let next_duration = self.next_duration(parent);
+        if next_duration > self.game_duration >> 1 {
+            return Err(GameError::ClockTimeExceeded);
+        }
+        if !self.claims.insert((pivot, next_position)) {
+            return Err(GameError::ClaimAlreadyExists);
+        }
+
+        self.claim_data[challenge_index].countered = true;
+        self.claim_data.push(ClaimData {
+            parent_index: challenge_index,
+            countered: false,
+            claim: pivot,
+            position: next_position,
+            clock: Clock {
+                duration: next_duration,
+                timestamp: self.timestamp,
+            },
+        });
+        Ok(())
+    }
+
+    /// Returns the duration that the team countering the given claim will have used once they
+    /// counter it at the current timestamp.
+    fn next_duration(&self, claim: &ClaimData) -> u64 {
+        let grandparent_duration = self
+            .claim_data
+            .get(claim.parent_index)
+            .map(|grandparent| grandparent.clock.duration)
+            .unwrap_or_default();
+        grandparent_duration + self.timestamp.saturating_sub(claim.clock.timestamp)
+    }
+
+    /// Returns whether the claim at `ancestor` is a strict ancestor of the claim at `index`.
+    fn is_ancestor(&self, ancestor: usize, mut index: usize) -> bool {
+        while let Some(claim) = self.claim_data.get(index) {
+            if claim.parent_index == ancestor {
+                return true;
+            }
+            index = claim.parent_index;
+        }
+        false
+    }
+
+    /// Returns an error if the game is not in progress.
+    fn assert_in_progress(&self) -> Result<(), GameError> {
+        if self.status != GameStatus::InProgress {
+            return Err(GameError::GameNotInProgress);
+        }
+        Ok(())
+    }
+
+    /// Executes a single step of the alphabet VM, returning the post state claim. The VM's state
+    /// transition is `(i, n) -> (i + 1, n + 1)`. If `pre_claim` is `None`, the step starts from the
+    /// absolute prestate and produces the state at trace index `0`.
+    fn alphabet_step(
+        &self,
+        pre_claim: Option<Claim>,
+        state_data: &Bytes,
+    ) -> Result<Claim, GameError> {
+        let (trace_index, state) = match pre_claim {
+            None => (
+                U256::zero(),
+                U256::from_big_endian(self.absolute_prestate.as_bytes()),
+            ),
+            Some(pre_claim) => {
+                if Claim::from(keccak256(state_data)) != pre_claim {
+                    return Err(GameError::InvalidPrestate);
+                }
+                let tokens = abi::decode(&[ParamType::Uint(256), ParamType::Uint(256)], state_data)
+                    .map_err(|_| GameError::InvalidPrestate)?;
+                match tokens.as_slice() {
+                    [Token::Uint(trace_index), Token::Uint(state)] => (*trace_index + 1, *state),
+                    _ => return Err(GameError::InvalidPrestate),
+                }
+            }
+        };
+
+        Ok(keccak256(abi::encode(&[
+            Token::Uint(trace_index),
+            Token::Uint(state + 1),
+        ]))
+        .into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FaultGameEmulator;
+    use crate::fault::{
+        providers::AlphabetTraceProvider, AlphabetGame, Claim, FaultGame, GameError, GameStatus,
+        Response,
+    };
+    use ethers::types::{Address, Bytes};
+    use std::sync::Arc;
+
+    const MAX_DEPTH: u64 = 4;
+    const GAME_DURATION: u64 = 60 * 60;
+    const HONEST_TRACE: [u8; 16] = [
+        16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31,
+    ];
+
+    /// Creates a new [AlphabetGame] for the given trace.
+    async fn player(trace: [u8; 16]) -> AlphabetGame {
+        AlphabetGame::new(
+            Address::zero(),
+            0,
+            MAX_DEPTH,
+            Claim::from_low_u64_be(15),
+            GAME_DURATION,
+            AlphabetTraceProvider::new(Arc::new(trace), MAX_DEPTH).unwrap(),
+        )
+        .await
+        .unwrap()
+    }
+
+    /// Plays a full game between two players, where player `0` makes the root claim. Each player
+    /// responds to every claim made by their opponent until neither has a move left, after which
+    /// the clocks run out and the game is resolved.
+    async fn play(traces: [[u8; 16]; 2]) -> GameStatus {
+        let mut players = [player(traces[0]).await, player(traces[1]).await];
+        let mut emulator = FaultGameEmulator::new(
+            players[0].claim_at(1).await.unwrap(),
+            MAX_DEPTH,
+            Claim::from_low_u64_be(15),
+            GAME_DURATION,
+            0,
+        );
+
+        let mut owners = vec![0];
+        let mut processed = 0;
+        while processed < emulator.claim_data.len() {
+            let len = emulator.claim_data.len();
+            for player in players.iter_mut() {
+                player.state = emulator.claim_data.clone();
+            }
+
+            for i in processed..len {
+                let responder = 1 - owners[i];
+                match players[responder].respond(i).await.unwrap() {
+                    Response::Move(is_attack, claim, secondary) => {
+                        if is_attack {
+                            emulator.attack(i, claim).unwrap();
+                        } else {
+                            emulator.defend(i, claim).unwrap();
+                        }
+                        owners.push(responder);
+
+                        if let Some((grandparent_index, claim)) = secondary {
+                            match emulator.attack(grandparent_index, claim) {
+                                Ok(()) => owners.push(responder),
+                                Err(GameError::ClaimAlreadyExists) => {}
+                                Err(e) => panic!("Unexpected error: {}", e),
+                            }
+                        }
+                    }
+                    Response::Step(state_index, claim_index, is_attack, state_data, proof) => {
+                        // A step that would revert with `ValidStep` is a failed attempt by the
+                        // dishonest player to counter a valid claim.
+                        match emulator.step(
+                            state_index,
+                            claim_index,
+                            is_attack,
+                            &state_data,
+                            &proof,
+                        ) {
+                            Ok(()) | Err(GameError::ValidStep) => {}
+                            Err(e) => panic!("Unexpected error: {}", e),
+                        }
+                    }
+                    Response::DoNothing => {}
+                }
+            }
+
+            processed = len;
+            emulator.warp(1);
+        }
+
+        assert_eq!(emulator.resolve(), Err(GameError::ClockNotExpired));
+        emulator.warp(GAME_DURATION);
+        emulator.resolve().unwrap()
+    }
+
+    #[tokio::test]
+    async fn honest_defender_wins() {
+        let mut dishonest = HONEST_TRACE;
+        dishonest[5..].iter_mut().for_each(|s| *s += 100);
+        assert_eq!(
+            play([HONEST_TRACE, dishonest]).await,
+            GameStatus::DefenderWins
+        );
+    }
+
+    #[tokio::test]
+    async fn honest_challenger_wins() {
+        let mut dishonest = HONEST_TRACE;
+        dishonest[11..].iter_mut().for_each(|s| *s += 100);
+        assert_eq!(
+            play([dishonest, HONEST_TRACE]).await,
+            GameStatus::ChallengerWins
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_moves() {
+        let mut emulator = FaultGameEmulator::new(
+            Claim::repeat_byte(1),
+            2,
+            Claim::from_low_u64_be(15),
+            GAME_DURATION,
+            0,
+        );
+
+        assert_eq!(
+            emulator.defend(0, Claim::repeat_byte(2)),
+            Err(GameError::CannotDefendRootClaim)
+        );
+        assert_eq!(
+            emulator.attack(1, Claim::repeat_byte(2)),
+            Err(GameError::InvalidParent)
+        );
+        emulator.attack(0, Claim::repeat_byte(2)).unwrap();
+        assert_eq!(
+            emulator.attack(0, Claim::repeat_byte(2)),
+            Err(GameError::ClaimAlreadyExists)
+        );
+        emulator.attack(1, Claim::repeat_byte(3)).unwrap();
+        assert_eq!(
+            emulator.attack(2, Claim::repeat_byte(4)),
+            Err(GameError::GameDepthExceeded)
+        );
+        assert_eq!(
+            emulator.step(0, 1, true, &Bytes::default(), &Bytes::default()),
+            Err(GameError::InvalidParent)
+        );
+
+        // The clock of the team countering claim 1 runs out after half of the game duration.
+        emulator.warp(GAME_DURATION / 2 + 1);
+        assert_eq!(
+            emulator.defend(1, Claim::repeat_byte(5)),
+            Err(GameError::ClockTimeExceeded)
+        );
+        assert_eq!(emulator.resolve(), Ok(GameStatus::DefenderWins));
+        assert_eq!(
+            emulator.attack(0, Claim::repeat_byte(6)),
+            Err(GameError::GameNotInProgress)
+        );
+    }
+}
@@ -1,10 +1,20 @@
 //! The game module holds the [FaultGame] trait.
 
-use super::{Claim, ClaimData, Response};
+use super::{Claim, ClaimData, GameStatus, Position, Resolution, Response};
 use anyhow::Result;
+use async_trait::async_trait;
+use ethers::types::Bytes;
+
+/// The maximum game depth supported by the solvers. Trace indices at this depth are still
+/// representable as a [u64].
+pub const MAX_SUPPORTED_DEPTH: u64 = 63;
 
 /// The [FaultGame] trait defines the interface for a local copy of an onchain fault dispute game.
-pub trait FaultGame<T> {
+///
+/// Implementors provide access to the game's DAG and to the trace they commit to by [Position];
+/// the solving logic in [FaultGame::respond] is shared between all implementations.
+#[async_trait]
+pub trait FaultGame<T>: Send + Sync {
     /// Respond to a [Claim] made by a participant in the dispute game.
     ///
     /// ### Takes
@@ -13,8 +23,162 @@ pub trait FaultGame<T> {
     /// ### Returns
     /// - `Ok(Response)`: The response to the claim.
     /// - `Err(anyhow::Error)`: An error occurred while determining the correct response to the
-    ///    [Claim].
-    fn respond(&self, parent_index: usize) -> Result<Response>;
+    ///   [Claim].
+    async fn respond(&self, parent_index: usize) -> Result<Response> {
+        let parent = self.claim_data(parent_index)?;
+
+        let mut is_attack = false;
+        let mut secondary_move_pos = None;
+
+        // Fetch our version of the parent claim.
+        let our_parent_claim = self.claim_at(parent.position).await?;
+
+        // There are 2 possible response options to the root claim:
+        // 1. Disagree with the root: Attack the root.
+        // 2. Agree with the root: Do nothing.
+        // There are 4 response options to a given claim that is *not* the root claim:
+        // 1. Disagree with the parent, agree with grandparent: Attack the parent.
+        // 2. Disagree with the parent, disagree with grandparent: Attack the parent *and* grandparent.
+        // 3. Agree with the parent, disagree with grandparent: Do nothing.
+        // 4. Agree with the parent, agree with grandparent: Defend the parent.
+        if our_parent_claim != parent.claim {
+            // We disagree with the parent; The move will always be an attack.
+            is_attack = true;
+
+            // If the parent is not the root, we check the grandparent as well.
+            if parent.parent_index as u32 != u32::MAX {
+                // Fetch our version of the grandparent claim.
+                let grandparent = self.claim_data(parent.parent_index)?;
+                let our_grandparent_claim = self.claim_at(grandparent.position).await?;
+                if our_grandparent_claim != grandparent.claim {
+                    // Attack the grandparent as a secondary move; We disagree with it as well.
+                    secondary_move_pos = Some(grandparent.position.make_move(is_attack));
+                }
+            }
+        } else {
+            // If we agree with the root claim, do nothing.
+            if parent.parent_index as u32 == u32::MAX {
+                return Ok(Response::DoNothing);
+            }
+
+            // Fetch our version of the grandparent claim. If we agree with it as well,
+            // we defend the parent claim.
+            let grandparent = self.claim_data(parent.parent_index)?;
+            let our_grandparent_claim = self.claim_at(grandparent.position).await?;
+            if our_grandparent_claim != grandparent.claim {
+                return Ok(Response::DoNothing);
+            }
+        }
+
+        // Compute the position of the primary move.
+        let move_pos = parent.position.make_move(is_attack);
+
+        // If we are past the maximum depth, perform a step.
+        // Otherwise, make a move.
+        if move_pos.depth() > self.max_depth() {
+            let mut state_index = 0;
+            let mut state_data = Bytes::default();
+
+            // First, we need to find the pre/post state index within the claim data depending
+            // on whether we are making an attack or defense step. If the index at depth of the
+            // move position is 0, it is an attack where the prestate is the absolute prestate. In
+            // this situation, the contract will determine the prestate itself and use the parent
+            // claim as the poststate.
+            if !is_attack || self.trace_index(parent.position)? > 0 {
+                let leaf_pos = if is_attack {
+                    parent.position - 1
+                } else {
+                    parent.position + 1
+                };
+
+                // Search for the index of the claim that commits to the `leaf_pos`' trace index.
+                // This claim must exist within the same path as the trace we're countering,
+                // so we can walk up the DAG starting from the parent and find the claim that
+                // commits to the same trace index as the `leaf_pos`.
+                let mut state = parent;
+                while state.position.right_index(self.max_depth()) != leaf_pos {
+                    state_index = state.parent_index;
+                    state = self.claim_data(state_index)?;
+                }
+
+                // Grab the state data for the prestate. The state data is the preimage for the
+                // prestate claim.
+                // If the move is an attack, the prestate of the step is at the trace index
+                // relative to `state`.
+                // If the move is a defense, the prestate of the step is at the trace index
+                // relative to `parent`.
+                state_data = if is_attack {
+                    self.preimage_at(state.position).await?
+                } else {
+                    self.preimage_at(parent.position).await?
+                };
+            }
+
+            // Fetch the proof for the step that produces the post state. If the move is an attack,
+            // the post state is the `parent`. If the move is a defense, the post state is the leaf
+            // to the right of the `parent`.
+            let post_state_pos = if is_attack {
+                parent.position
+            } else {
+                parent.position + 1
+            };
+            let proof = self.proof_at(post_state_pos).await?;
+
+            Ok(Response::Step(
+                state_index,
+                parent_index,
+                is_attack,
+                state_data,
+                proof,
+            ))
+        } else {
+            let secondary_move = match secondary_move_pos {
+                Some(pos) => Some((
+                    parent.parent_index,
+                    self.claim_at(pos).await.unwrap_or_default(),
+                )),
+                None => None,
+            };
+            Ok(Response::Move(
+                is_attack,
+                self.claim_at(move_pos).await?,
+                secondary_move,
+            ))
+        }
+    }
+
+    /// Computes the [Resolution] of the local copy of the game DAG.
+    ///
+    /// ### Returns
+    /// - `Ok(Resolution)`: The predicted outcome of the game and when it can be resolved.
+    /// - `Err(anyhow::Error)`: The game DAG is empty or malformed.
+    fn resolution(&self) -> Result<Resolution> {
+        Resolution::new(self.state(), self.max_depth(), self.game_duration())
+    }
+
+    /// Returns the [GameStatus] that the game resolves to if it is played correctly. The defender
+    /// should win if we agree with the root claim, and the challenger otherwise.
+    ///
+    /// ### Returns
+    /// - `Ok(GameStatus)`: The expected status of the game.
+    /// - `Err(anyhow::Error)`: An error occurred while fetching our version of the root claim.
+    async fn expected_status(&self) -> Result<GameStatus> {
+        let root = self.claim_data(0)?;
+        if self.claim_at(root.position).await? == root.claim {
+            Ok(GameStatus::DefenderWins)
+        } else {
+            Ok(GameStatus::ChallengerWins)
+        }
+    }
+
+    /// Returns the maximum depth of the game tree.
+    fn max_depth(&self) -> u64;
+
+    /// Returns the total duration of the game's chess clocks, in seconds.
+    fn game_duration(&self) -> u64;
+
+    /// Returns the local copy of the game DAG.
+    fn state(&self) -> &[ClaimData];
 
     /// Fetch the [ClaimData] at the given index in the DAG array.
     ///
@@ -35,7 +199,7 @@ pub trait FaultGame<T> {
     /// ### Returns
     /// - `Ok(T)`: The state at the given position.
     /// - `Err(anyhow::Error)`: An error occurred while fetching the state.
-    fn state_at(&self, position: u128) -> Result<T>;
+    async fn state_at(&self, position: u128) -> Result<T>;
 
     /// Fetch the [Claim] at the given position in the game tree. This [Claim] is always true in
     /// the relative view of the participant.
@@ -46,5 +210,37 @@ pub trait FaultGame<T> {
     /// ### Returns
     /// - `Ok(Claim)`: The [Claim] at the given position.
     /// - `Err(anyhow::Error)`: An error occurred while fetching the claim.
-    fn claim_at(&self, position: u128) -> Result<Claim>;
+    async fn claim_at(&self, position: u128) -> Result<Claim>;
+
+    /// Fetch the trace index that the given position commits to, relative to the trace that the
+    /// position belongs to.
+    ///
+    /// ### Takes
+    /// - `position`: The position within the game tree.
+    ///
+    /// ### Returns
+    /// - `Ok(u64)`: The trace index that the position commits to.
+    /// - `Err(anyhow::Error)`: The position is not within the game tree.
+    fn trace_index(&self, position: u128) -> Result<u64>;
+
+    /// Fetch the preimage of the [Claim] at the given position in the game tree. This is the
+    /// state data passed to the VM when stepping from the [Claim].
+    ///
+    /// ### Takes
+    /// - `position`: The position of the claim within the game tree.
+    ///
+    /// ### Returns
+    /// - `Ok(Bytes)`: The preimage of the [Claim] at the given position.
+    /// - `Err(anyhow::Error)`: An error occurred while fetching the preimage.
+    async fn preimage_at(&self, position: u128) -> Result<Bytes>;
+
+    /// Fetch the proof for the VM step that produces the state at the given leaf position.
+    ///
+    /// ### Takes
+    /// - `position`: The leaf position of the post state of the step.
+    ///
+    /// ### Returns
+    /// - `Ok(Bytes)`: The step proof.
+    /// - `Err(anyhow::Error)`: An error occurred while fetching the proof.
+    async fn proof_at(&self, position: u128) -> Result<Bytes>;
 }
@@ -8,7 +8,21 @@ mod types;
 pub use types::*;
 
 mod game;
-pub use game::FaultGame;
+pub use game::{FaultGame, MAX_SUPPORTED_DEPTH};
 
-mod alphabet;
-pub use alphabet::AlphabetGame;
+mod resolution;
+pub use resolution::{expires_at, Resolution};
+
+mod provider;
+pub use provider::{BottomTraceFactory, TraceProvider};
+
+pub mod providers;
+
+mod solver;
+pub use solver::{AlphabetGame, CannonGame, FaultSolver, OutputGame};
+
+mod split;
+pub use split::{OutputCannonGame, SplitSolver, SplitState};
+
+mod emulator;
+pub use emulator::FaultGameEmulator;
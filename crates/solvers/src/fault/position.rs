@@ -19,6 +19,12 @@ pub trait Position {
     fn trace_index(&self, max_depth: u64) -> u64;
     /// Returns the relative [Position] for an attack or defense move against the current [Position].
     fn make_move(&self, is_attack: bool) -> Self;
+    /// Returns the ancestor of the current [Position] at the given depth.
+    /// Panics if the depth is deeper than the current [Position].
+    fn ancestor_at_depth(&self, depth: u64) -> Self;
+    /// Returns the [Position] relative to the subtree rooted at the current [Position]'s ancestor
+    /// at the given depth. Panics if the depth is deeper than the current [Position].
+    fn relative_to_ancestor_at_depth(&self, depth: u64) -> Self;
 }
 
 /// Computes a generalized index from a depth and index at depth.
@@ -67,11 +73,30 @@ impl Position for u128 {
     fn make_move(&self, is_attack: bool) -> Self {
         ((!is_attack as u128) | self) << 1
     }
+
+    fn ancestor_at_depth(&self, depth: u64) -> Self {
+        assert!(
+            depth <= self.depth(),
+            "Ancestor depth {depth} is deeper than the position's depth {}",
+            self.depth()
+        );
+        self >> (self.depth() - depth)
+    }
+
+    fn relative_to_ancestor_at_depth(&self, depth: u64) -> Self {
+        assert!(
+            depth <= self.depth(),
+            "Ancestor depth {depth} is deeper than the position's depth {}",
+            self.depth()
+        );
+        let relative_depth = self.depth() - depth;
+        (1 << relative_depth) | (self & ((1 << relative_depth) - 1))
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use super::Position;
+    use super::{compute_gindex, Position};
 
     /// A helper struct for testing the [Position] trait implementation for [std::u128].
     /// 0. `u64` - `depth`
@@ -126,4 +151,27 @@ mod test {
             assert_eq!(r.index_at_depth(), v.3);
         }
     }
+
+    #[test]
+    fn position_relative_to_ancestor() {
+        // A split tree with a split depth of 2 and a max depth of 4.
+        let pos = compute_gindex(4, 13);
+        assert_eq!(pos.ancestor_at_depth(2), compute_gindex(2, 3));
+        assert_eq!(pos.ancestor_at_depth(4), pos);
+        assert_eq!(pos.ancestor_at_depth(0), 1);
+
+        // Relative to the subtree rooted at depth 3, the position is the right child of the root.
+        assert_eq!(pos.relative_to_ancestor_at_depth(3), 3);
+        assert_eq!(pos.relative_to_ancestor_at_depth(3).trace_index(1), 1);
+        // Relative to the subtree rooted at depth 2, the position is the second leaf of the root.
+        let relative = pos.relative_to_ancestor_at_depth(2);
+        assert_eq!(relative, compute_gindex(2, 1));
+        assert_eq!(relative.trace_index(2), 1);
+        // A position relative to itself is the root.
+        assert_eq!(pos.relative_to_ancestor_at_depth(4), 1);
+
+        // A position has no ancestors deeper than itself.
+        assert!(std::panic::catch_unwind(|| pos.ancestor_at_depth(5)).is_err());
+        assert!(std::panic::catch_unwind(|| pos.relative_to_ancestor_at_depth(5)).is_err());
+    }
 }
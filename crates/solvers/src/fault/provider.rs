@@ -0,0 +1,88 @@
+//! The provider module holds the [TraceProvider] trait.
+
+use super::Claim;
+use anyhow::Result;
+use async_trait::async_trait;
+use ethers::{types::Bytes, utils::keccak256};
+
+/// The [TraceProvider] trait defines the interface for a source of an execution trace that a
+/// [FaultGame](super::FaultGame) solver commits to. Implementations may hold the full trace in
+/// memory or compute states lazily as they are requested.
+#[async_trait]
+pub trait TraceProvider<T>: Send + Sync {
+    /// Fetch the absolute prestate of the trace. This is the state that precedes the state at
+    /// trace index `0`.
+    ///
+    /// ### Returns
+    /// - `Ok(Claim)`: The commitment to the absolute prestate.
+    /// - `Err(anyhow::Error)`: An error occurred while fetching the absolute prestate.
+    async fn absolute_prestate(&self) -> Result<Claim>;
+
+    /// Fetch the raw state at the given trace index.
+    ///
+    /// ### Takes
+    /// - `index`: The trace index.
+    ///
+    /// ### Returns
+    /// - `Ok(T)`: The state at the given trace index.
+    /// - `Err(anyhow::Error)`: An error occurred while fetching the state.
+    async fn state_at(&self, index: u64) -> Result<T>;
+
+    /// Fetch the preimage of the [Claim] at the given trace index. This is the state data that is
+    /// passed to the VM when stepping from the state at the given trace index.
+    ///
+    /// ### Takes
+    /// - `index`: The trace index.
+    ///
+    /// ### Returns
+    /// - `Ok(Bytes)`: The preimage of the [Claim] at the given trace index.
+    /// - `Err(anyhow::Error)`: An error occurred while fetching the preimage.
+    async fn preimage_at(&self, index: u64) -> Result<Bytes>;
+
+    /// Fetch the [Claim] at the given trace index. By default, this is the keccak256 hash of the
+    /// preimage at the given trace index.
+    ///
+    /// ### Takes
+    /// - `index`: The trace index.
+    ///
+    /// ### Returns
+    /// - `Ok(Claim)`: The [Claim] at the given trace index.
+    /// - `Err(anyhow::Error)`: An error occurred while fetching the claim.
+    async fn claim_at(&self, index: u64) -> Result<Claim> {
+        Ok(keccak256(self.preimage_at(index).await?).into())
+    }
+
+    /// Fetch the proof required to execute the single step of the VM that produces the state at
+    /// the given trace index. For trace index `0`, this is the step from the absolute prestate.
+    ///
+    /// ### Takes
+    /// - `index`: The trace index of the post state of the step.
+    ///
+    /// ### Returns
+    /// - `Ok(Bytes)`: The step proof.
+    /// - `Err(anyhow::Error)`: An error occurred while fetching the proof.
+    async fn proof_at(&self, index: u64) -> Result<Bytes>;
+}
+
+/// The [BottomTraceFactory] trait defines the interface for creating the [TraceProvider]s of the
+/// lower half of a split-depth game. Each subtree below the split depth disputes a single
+/// transition of the upper trace, and is played over its own execution trace.
+pub trait BottomTraceFactory<Top>: Send + Sync {
+    /// The type of the states in the execution traces.
+    type State;
+    /// The [TraceProvider] for the execution traces.
+    type Provider: TraceProvider<Self::State>;
+
+    /// Creates the [TraceProvider] for the execution trace that transitions the upper trace from
+    /// the state at `top_index - 1` (or the absolute prestate of the upper trace, if `top_index`
+    /// is `0`) to the state at `top_index`.
+    ///
+    /// ### Takes
+    /// - `top`: The [TraceProvider] of the upper half of the game.
+    /// - `top_index`: The trace index of the post state in the upper trace.
+    ///
+    /// ### Returns
+    /// - `Ok(Self::Provider)`: The [TraceProvider] for the execution trace.
+    /// - `Err(anyhow::Error)`: An error occurred while creating the provider.
+    fn provider_for(&self, top: &Top, top_index: u64) -> Result<Self::Provider>;
+}
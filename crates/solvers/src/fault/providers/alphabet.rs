@@ -0,0 +1,141 @@
+//! The alphabet module contains the [TraceProvider] implementation for the alphabet VM.
+
+use crate::fault::{Claim, TraceProvider};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use ethers::{
+    abi::{self, Token},
+    types::{Bytes, U256},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// The [AlphabetTraceProvider] serves the trace of the alphabet VM from memory. The VM's state
+/// transition is `n -> n + 1`.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlphabetTraceProvider {
+    /// Our full execution trace. If the trace is shorter than `2^max_depth`, it is padded by
+    /// repeating the final state.
+    pub trace: Arc<[u8]>,
+    /// The maximum depth of the game tree that the trace is committed to.
+    pub max_depth: u64,
+}
+
+impl AlphabetTraceProvider {
+    /// Creates a new [AlphabetTraceProvider].
+    ///
+    /// ### Takes
+    /// - `trace`: Our execution trace. Must contain at least one and at most `2^max_depth` states.
+    /// - `max_depth`: The maximum depth of the game tree.
+    ///
+    /// ### Returns
+    /// - `Ok(AlphabetTraceProvider)`: The new provider.
+    /// - `Err(anyhow::Error)`: The trace does not fit within the game tree.
+    pub fn new(trace: Arc<[u8]>, max_depth: u64) -> Result<Self> {
+        if trace.is_empty() {
+            anyhow::bail!("The trace must contain at least one state");
+        }
+        if trace.len() as u128 > num_leaves(max_depth) {
+            anyhow::bail!(
+                "Trace length {} exceeds the number of leaves at max depth {}",
+                trace.len(),
+                max_depth
+            );
+        }
+        Self::compute_absolute_prestate(&trace)?;
+
+        Ok(Self { trace, max_depth })
+    }
+
+    /// Computes the absolute prestate of the alphabet VM for the given trace. The VM's state
+    /// transition is `n -> n + 1`, so the absolute prestate is the state preceding the first
+    /// state of the trace.
+    pub fn compute_absolute_prestate(trace: &[u8]) -> Result<Claim> {
+        let first = trace.first().ok_or(anyhow!("Empty trace"))?;
+        let prestate = first
+            .checked_sub(1)
+            .ok_or(anyhow!("The first state of the trace has no prestate"))?;
+        Ok(Claim::from_low_u64_be(prestate as u64))
+    }
+}
+
+/// Returns the number of leaves in a game tree with the given maximum depth.
+fn num_leaves(max_depth: u64) -> u128 {
+    1u128.checked_shl(max_depth as u32).unwrap_or(u128::MAX)
+}
+
+#[async_trait]
+impl TraceProvider<u8> for AlphabetTraceProvider {
+    async fn absolute_prestate(&self) -> Result<Claim> {
+        Self::compute_absolute_prestate(&self.trace)
+    }
+
+    async fn state_at(&self, index: u64) -> Result<u8> {
+        if index as u128 >= num_leaves(self.max_depth) {
+            anyhow::bail!("Trace index {} is out of bounds", index);
+        }
+
+        // Trace indices past the end of our trace are padded with the final state.
+        let last_index = self.trace.len() - 1;
+        self.trace
+            .get(usize::try_from(index).map_or(last_index, |i| i.min(last_index)))
+            .copied()
+            .ok_or(anyhow!("Invalid trace index"))
+    }
+
+    async fn preimage_at(&self, index: u64) -> Result<Bytes> {
+        Ok(abi::encode(&[
+            Token::Uint(U256::from(index)),
+            Token::Uint(U256::from(self.state_at(index).await?)),
+        ])
+        .into())
+    }
+
+    async fn proof_at(&self, _: u64) -> Result<Bytes> {
+        // The alphabet VM does not require a proof to step.
+        Ok(Bytes::default())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::AlphabetTraceProvider;
+    use crate::fault::{Claim, TraceProvider};
+    use std::sync::Arc;
+
+    const TRACE: [u8; 16] = [
+        16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31,
+    ];
+
+    #[test]
+    fn new_rejects_invalid_traces() {
+        assert!(AlphabetTraceProvider::new(Arc::new(TRACE), 3).is_err());
+        assert!(AlphabetTraceProvider::new(Arc::new([]), 4).is_err());
+        assert!(AlphabetTraceProvider::new(Arc::new([0]), 4).is_err());
+        assert!(AlphabetTraceProvider::new(Arc::new(TRACE), 4).is_ok());
+    }
+
+    #[tokio::test]
+    async fn state_at_pads_trace() {
+        let provider = AlphabetTraceProvider::new(Arc::new(TRACE), 6).unwrap();
+
+        // Within the trace.
+        assert_eq!(provider.state_at(0).await.unwrap(), 16);
+        assert_eq!(provider.state_at(15).await.unwrap(), 31);
+        // Past the end of the trace, the final state is repeated.
+        assert_eq!(provider.state_at(16).await.unwrap(), 31);
+        assert_eq!(provider.state_at(63).await.unwrap(), 31);
+        // Past the end of the game tree.
+        assert!(provider.state_at(64).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn absolute_prestate() {
+        let provider = AlphabetTraceProvider::new(Arc::new(TRACE), 4).unwrap();
+        assert_eq!(
+            provider.absolute_prestate().await.unwrap(),
+            Claim::from_low_u64_be(15)
+        );
+    }
+}
@@ -0,0 +1,336 @@
+//! The cannon module contains the [TraceProvider] implementation for execution traces produced by
+//! an external Cannon-style VM binary.
+
+use super::OutputTraceProvider;
+use crate::fault::{BottomTraceFactory, Claim, TraceProvider};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use ethers::types::Bytes;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tokio::{process::Command, sync::Mutex};
+
+/// The [CannonTraceProvider] sources an execution trace by running an external Cannon-style VM
+/// executable as a subprocess. Step proofs produced by the VM are cached on disk, so each step of
+/// the trace is only ever executed once.
+///
+/// Trace index `i` commits to the state after `i + 1` steps of the VM have been executed from the
+/// absolute prestate. If the VM exits before a requested step, the trace is padded with the final
+/// state of the VM.
+///
+/// The VM is invoked as:
+/// ```text
+/// <vm> run --input <prestate> --output <cache_dir>/final.json --proof-at =<step> \
+///     --stop-at =<step + 1> --proof-fmt <cache_dir>/proofs/%d.json [args...]
+/// ```
+/// and must write the proof for `<step>` to `<cache_dir>/proofs/<step>.json` as a JSON object with
+/// the `pre`, `post`, `state-data` and `proof-data` fields. If the VM exits before `<step>`, it must
+/// write its final state, including its `step` count, to the `--output` path.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CannonTraceProvider {
+    /// The path to the VM executable.
+    pub vm: PathBuf,
+    /// The path to the absolute prestate file passed to the VM as its input.
+    pub prestate: PathBuf,
+    /// The directory that the VM's step proofs and final state are cached in.
+    pub cache_dir: PathBuf,
+    /// Additional arguments appended to every invocation of the VM.
+    pub args: Vec<String>,
+    /// Serializes invocations of the VM so that concurrent requests do not race on the cache.
+    #[serde(skip)]
+    run_lock: Mutex<()>,
+}
+
+/// A step proof generated by the VM.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct StepProof {
+    /// The commitment to the state before the step.
+    pre: Claim,
+    /// The encoded state before the step.
+    state_data: Bytes,
+    /// The proof data required to execute the step onchain.
+    proof_data: Bytes,
+}
+
+/// The final state of the VM, written when it exits.
+#[derive(Debug, Deserialize)]
+struct FinalState {
+    /// The number of steps the VM executed before exiting.
+    step: u64,
+}
+
+impl CannonTraceProvider {
+    /// Creates a new [CannonTraceProvider].
+    ///
+    /// ### Takes
+    /// - `vm`: The path to the VM executable.
+    /// - `prestate`: The path to the absolute prestate file.
+    /// - `cache_dir`: The directory that step proofs are cached in.
+    /// - `args`: Additional arguments appended to every invocation of the VM.
+    pub fn new(vm: PathBuf, prestate: PathBuf, cache_dir: PathBuf, args: Vec<String>) -> Self {
+        Self {
+            vm,
+            prestate,
+            cache_dir,
+            args,
+            run_lock: Mutex::default(),
+        }
+    }
+
+    /// Returns the path of the cached proof for the given step.
+    fn proof_path(&self, step: u64) -> PathBuf {
+        self.cache_dir.join("proofs").join(format!("{}.json", step))
+    }
+
+    /// Returns the path of the VM's final state.
+    fn final_state_path(&self) -> PathBuf {
+        self.cache_dir.join("final.json")
+    }
+
+    /// Fetches the step proof for the given step, running the VM if it is not yet cached. If the
+    /// VM exits before the given step, the proof for its final step is returned instead.
+    async fn step_proof(&self, step: u64) -> Result<StepProof> {
+        let _guard = self.run_lock.lock().await;
+
+        if let Some(proof) = self.cached_proof(step).await? {
+            return Ok(proof);
+        }
+        self.run(step).await?;
+        if let Some(proof) = self.cached_proof(step).await? {
+            return Ok(proof);
+        }
+
+        // The VM did not produce a proof for the step, so it must have exited beforehand. Pad the
+        // trace with the final state.
+        let final_state: FinalState =
+            serde_json::from_slice(&tokio::fs::read(self.final_state_path()).await?)?;
+        if final_state.step >= step {
+            anyhow::bail!("VM did not produce a proof for step {}", step);
+        }
+        if self.cached_proof(final_state.step).await?.is_none() {
+            self.run(final_state.step).await?;
+        }
+        self.cached_proof(final_state.step).await?.ok_or(anyhow!(
+            "VM did not produce a proof for its final step {}",
+            final_state.step
+        ))
+    }
+
+    /// Reads the cached proof for the given step, if it exists.
+    async fn cached_proof(&self, step: u64) -> Result<Option<StepProof>> {
+        match tokio::fs::read(self.proof_path(step)).await {
+            Ok(raw) => Ok(Some(serde_json::from_slice(&raw)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Runs the VM to generate the proof for the given step.
+    async fn run(&self, step: u64) -> Result<()> {
+        tokio::fs::create_dir_all(self.cache_dir.join("proofs")).await?;
+
+        tracing::debug!(target: "cannon-trace-provider", "Running VM to generate the proof for step {}", step);
+        let output = Command::new(&self.vm)
+            .arg("run")
+            .arg("--input")
+            .arg(&self.prestate)
+            .arg("--output")
+            .arg(self.final_state_path())
+            .arg("--proof-at")
+            .arg(format!("={}", step))
+            .arg("--stop-at")
+            .arg(format!("={}", step + 1))
+            .arg("--proof-fmt")
+            .arg(self.cache_dir.join("proofs").join("%d.json"))
+            .args(&self.args)
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "VM exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TraceProvider<Bytes> for CannonTraceProvider {
+    async fn absolute_prestate(&self) -> Result<Claim> {
+        Ok(self.step_proof(0).await?.pre)
+    }
+
+    async fn state_at(&self, index: u64) -> Result<Bytes> {
+        self.preimage_at(index).await
+    }
+
+    async fn preimage_at(&self, index: u64) -> Result<Bytes> {
+        Ok(self.step_proof(index + 1).await?.state_data)
+    }
+
+    async fn claim_at(&self, index: u64) -> Result<Claim> {
+        Ok(self.step_proof(index + 1).await?.pre)
+    }
+
+    async fn proof_at(&self, index: u64) -> Result<Bytes> {
+        Ok(self.step_proof(index).await?.proof_data)
+    }
+}
+
+/// The [CannonTraceFactory] creates a [CannonTraceProvider] for each L2 block disputed in the lower
+/// half of an output bisection game. Every occurrence of `{l2_block}` in the configured `args` is
+/// substituted with the number of the disputed L2 block, and each block's proofs are cached in
+/// their own subdirectory of the `cache_dir`.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CannonTraceFactory {
+    /// The path to the VM executable.
+    pub vm: PathBuf,
+    /// The path to the absolute prestate file passed to the VM as its input.
+    pub prestate: PathBuf,
+    /// The directory that each block's step proofs are cached in.
+    pub cache_dir: PathBuf,
+    /// Additional arguments appended to every invocation of the VM.
+    pub args: Vec<String>,
+}
+
+impl BottomTraceFactory<OutputTraceProvider> for CannonTraceFactory {
+    type State = Bytes;
+    type Provider = CannonTraceProvider;
+
+    fn provider_for(&self, top: &OutputTraceProvider, top_index: u64) -> Result<Self::Provider> {
+        let l2_block = top.block_at(top_index).to_string();
+        Ok(CannonTraceProvider::new(
+            self.vm.clone(),
+            self.prestate.clone(),
+            self.cache_dir.join(&l2_block),
+            self.args
+                .iter()
+                .map(|arg| arg.replace("{l2_block}", &l2_block))
+                .collect(),
+        ))
+    }
+}
+
+#[cfg(all(test, unix))]
+mod test {
+    use super::CannonTraceProvider;
+    use crate::fault::{Claim, TraceProvider};
+    use ethers::types::Bytes;
+    use std::{fs, os::unix::fs::PermissionsExt, path::Path};
+
+    /// A stub VM that exits after 10 steps. The commitment to the state after `n` steps is `n`.
+    const STUB_VM: &str = r#"#!/bin/sh
+echo "$@" >> "$(dirname "$0")/invocations"
+while [ $# -gt 0 ]; do
+    case "$1" in
+        --output) output="$2"; shift 2 ;;
+        --proof-at) step="${2#=}"; shift 2 ;;
+        --proof-fmt) fmt="$2"; shift 2 ;;
+        *) shift ;;
+    esac
+done
+if [ "$step" -gt 10 ]; then
+    echo '{"step": 10, "exited": true}' > "$output"
+    exit 0
+fi
+post=$((step < 10 ? step + 1 : 10))
+printf '{"step": %d, "pre": "0x%064x", "post": "0x%064x", "state-data": "0x%02x", "proof-data": "0xff%02x"}' \
+    "$step" "$step" "$post" "$step" "$step" > "$(printf "$fmt" "$step")"
+"#;
+
+    /// Writes the stub VM to the given directory and returns a provider that uses it.
+    fn provider(dir: &Path) -> CannonTraceProvider {
+        let vm = dir.join("vm");
+        fs::write(&vm, STUB_VM).unwrap();
+        fs::set_permissions(&vm, fs::Permissions::from_mode(0o755)).unwrap();
+        CannonTraceProvider::new(
+            vm,
+            dir.join("prestate.json"),
+            dir.join("cache"),
+            Vec::default(),
+        )
+    }
+
+    /// Returns the number of times the stub VM has been invoked.
+    fn invocations(dir: &Path) -> usize {
+        fs::read_to_string(dir.join("invocations"))
+            .map(|s| s.lines().count())
+            .unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn serves_trace_from_vm() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = provider(dir.path());
+
+        assert_eq!(
+            provider.absolute_prestate().await.unwrap(),
+            Claim::from_low_u64_be(0)
+        );
+        assert_eq!(
+            provider.claim_at(3).await.unwrap(),
+            Claim::from_low_u64_be(4)
+        );
+        assert_eq!(
+            provider.preimage_at(3).await.unwrap(),
+            Bytes::from(vec![0x04])
+        );
+        assert_eq!(
+            provider.proof_at(3).await.unwrap(),
+            Bytes::from(vec![0xff, 0x03])
+        );
+    }
+
+    #[tokio::test]
+    async fn caches_proofs_on_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = provider(dir.path());
+
+        provider.claim_at(5).await.unwrap();
+        provider.preimage_at(5).await.unwrap();
+        assert_eq!(invocations(dir.path()), 1);
+
+        // A new provider with the same cache directory does not re-run the VM.
+        let provider = CannonTraceProvider::new(
+            provider.vm.clone(),
+            provider.prestate.clone(),
+            provider.cache_dir.clone(),
+            Vec::default(),
+        );
+        provider.claim_at(5).await.unwrap();
+        assert_eq!(invocations(dir.path()), 1);
+    }
+
+    #[tokio::test]
+    async fn pads_trace_with_final_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = provider(dir.path());
+
+        assert_eq!(
+            provider.claim_at(100).await.unwrap(),
+            Claim::from_low_u64_be(10)
+        );
+        assert_eq!(
+            provider.proof_at(100).await.unwrap(),
+            Bytes::from(vec![0xff, 0x0a])
+        );
+    }
+
+    #[tokio::test]
+    async fn surfaces_vm_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = CannonTraceProvider::new(
+            dir.path().join("missing-vm"),
+            dir.path().join("prestate.json"),
+            dir.path().join("cache"),
+            Vec::default(),
+        );
+        assert!(provider.claim_at(0).await.is_err());
+    }
+}
@@ -0,0 +1,10 @@
+//! Implementations of the [TraceProvider](super::TraceProvider) trait.
+
+mod alphabet;
+pub use alphabet::AlphabetTraceProvider;
+
+mod cannon;
+pub use cannon::{CannonTraceFactory, CannonTraceProvider};
+
+mod output;
+pub use output::{BlockRef, NodeClient, OutputAtBlock, OutputTraceProvider};
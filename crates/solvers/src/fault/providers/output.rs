@@ -0,0 +1,299 @@
+//! The output module contains the [TraceProvider] implementation for traces of L2 output roots.
+
+use crate::fault::{Claim, TraceProvider};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use ethers::{
+    providers::{Http, JsonRpcClient, Provider},
+    types::{Bytes, H256},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{Arc, Mutex, OnceLock},
+};
+
+/// The [OutputTraceProvider] serves a trace of trusted L2 output roots over a range of L2 blocks,
+/// fetched from a trusted op-node via `optimism_outputAtBlock`.
+///
+/// The absolute prestate is the output root at the `prestate_block`, and trace index `i` commits
+/// to the output root at L2 block `prestate_block + i + 1`. Trace indices past the
+/// `poststate_block` are padded with the output root at the `poststate_block`.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", bound = "")]
+pub struct OutputTraceProvider<C: NodeClient = Http> {
+    /// The URL of the trusted op-node RPC.
+    pub rpc_url: String,
+    /// The L2 block number of the agreed upon output root that the trace starts from.
+    pub prestate_block: u64,
+    /// The L2 block number of the disputed output root that the trace ends at.
+    pub poststate_block: u64,
+    /// The lazily connected client for the trusted op-node RPC.
+    #[serde(skip)]
+    client: OnceLock<Arc<Provider<C>>>,
+    /// Output roots that have already been fetched, keyed by L2 block number.
+    #[serde(skip)]
+    cache: Mutex<HashMap<u64, OutputAtBlock>>,
+}
+
+/// The response of the `optimism_outputAtBlock` RPC method.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputAtBlock {
+    /// The version of the output root.
+    pub version: H256,
+    /// The output root.
+    pub output_root: H256,
+    /// The state root of the L2 block.
+    pub state_root: H256,
+    /// The storage root of the `L2ToL1MessagePasser` contract.
+    pub withdrawal_storage_root: H256,
+    /// The L2 block that the output root commits to.
+    pub block_ref: BlockRef,
+}
+
+/// A reference to an L2 block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockRef {
+    /// The hash of the L2 block.
+    pub hash: H256,
+}
+
+/// A [JsonRpcClient] for the trusted op-node RPC that the [OutputTraceProvider] can reconnect
+/// with from its URL, after being deserialized.
+pub trait NodeClient: JsonRpcClient + 'static {
+    /// Returns the URL of the RPC.
+    fn url(&self) -> String;
+
+    /// Connects to the RPC at the given URL.
+    fn connect(url: &str) -> Result<Self>
+    where
+        Self: Sized;
+}
+
+impl NodeClient for Http {
+    fn url(&self) -> String {
+        Http::url(self).to_string()
+    }
+
+    fn connect(url: &str) -> Result<Self> {
+        Ok(Http::from_str(url)?)
+    }
+}
+
+impl<C: NodeClient> OutputTraceProvider<C> {
+    /// Creates a new [OutputTraceProvider].
+    ///
+    /// ### Takes
+    /// - `node_provider`: The client for the trusted op-node RPC.
+    /// - `prestate_block`: The L2 block number of the agreed upon output root.
+    /// - `poststate_block`: The L2 block number of the disputed output root.
+    ///
+    /// ### Returns
+    /// - `Ok(OutputTraceProvider)`: The new provider.
+    /// - `Err(anyhow::Error)`: The block range is empty.
+    pub fn new(
+        node_provider: Arc<Provider<C>>,
+        prestate_block: u64,
+        poststate_block: u64,
+    ) -> Result<Self> {
+        if poststate_block <= prestate_block {
+            anyhow::bail!(
+                "Invalid block range; poststate block {} must be greater than prestate block {}",
+                poststate_block,
+                prestate_block
+            );
+        }
+
+        Ok(Self {
+            rpc_url: AsRef::<C>::as_ref(node_provider.as_ref()).url(),
+            prestate_block,
+            poststate_block,
+            client: OnceLock::from(node_provider),
+            cache: Mutex::default(),
+        })
+    }
+
+    /// Returns the L2 block number that the given trace index commits to.
+    pub fn block_at(&self, index: u64) -> u64 {
+        self.prestate_block
+            .saturating_add(index)
+            .saturating_add(1)
+            .min(self.poststate_block)
+    }
+
+    /// Fetches the trusted output at the given L2 block number.
+    pub async fn output_at_block(&self, block_number: u64) -> Result<OutputAtBlock> {
+        if let Some(output) = self
+            .cache
+            .lock()
+            .map_err(|_| anyhow!("Output cache poisoned"))?
+            .get(&block_number)
+        {
+            return Ok(output.clone());
+        }
+
+        let output: OutputAtBlock = self
+            .client()?
+            .request(
+                "optimism_outputAtBlock",
+                vec![format!("0x{:x}", block_number)],
+            )
+            .await?;
+
+        self.cache
+            .lock()
+            .map_err(|_| anyhow!("Output cache poisoned"))?
+            .insert(block_number, output.clone());
+        Ok(output)
+    }
+
+    /// Returns the client for the trusted op-node RPC, connecting to it if necessary.
+    fn client(&self) -> Result<&Arc<Provider<C>>> {
+        if let Some(client) = self.client.get() {
+            return Ok(client);
+        }
+        let client = Arc::new(Provider::new(C::connect(&self.rpc_url)?));
+        Ok(self.client.get_or_init(|| client))
+    }
+}
+
+#[async_trait]
+impl<C: NodeClient> TraceProvider<H256> for OutputTraceProvider<C> {
+    async fn absolute_prestate(&self) -> Result<Claim> {
+        Ok(self.output_at_block(self.prestate_block).await?.output_root)
+    }
+
+    async fn state_at(&self, index: u64) -> Result<H256> {
+        Ok(self
+            .output_at_block(self.block_at(index))
+            .await?
+            .output_root)
+    }
+
+    async fn preimage_at(&self, index: u64) -> Result<Bytes> {
+        let output = self.output_at_block(self.block_at(index)).await?;
+        Ok([
+            output.version.as_bytes(),
+            output.state_root.as_bytes(),
+            output.withdrawal_storage_root.as_bytes(),
+            output.block_ref.hash.as_bytes(),
+        ]
+        .concat()
+        .into())
+    }
+
+    async fn claim_at(&self, index: u64) -> Result<Claim> {
+        self.state_at(index).await
+    }
+
+    async fn proof_at(&self, index: u64) -> Result<Bytes> {
+        anyhow::bail!(
+            "Output root traces can not be stepped; bisection ended at L2 block {}",
+            self.block_at(index)
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BlockRef, NodeClient, OutputAtBlock, OutputTraceProvider};
+    use crate::fault::TraceProvider;
+    use ethers::{
+        providers::{Http, MockProvider, Provider},
+        types::{Bytes, H256},
+    };
+    use std::sync::Arc;
+
+    impl NodeClient for MockProvider {
+        fn url(&self) -> String {
+            "mock".to_string()
+        }
+
+        fn connect(_: &str) -> anyhow::Result<Self> {
+            anyhow::bail!("Mock providers can not reconnect")
+        }
+    }
+
+    /// Creates a new [OutputTraceProvider] over the given block range.
+    fn provider(prestate_block: u64, poststate_block: u64) -> anyhow::Result<OutputTraceProvider> {
+        OutputTraceProvider::new(
+            Arc::new(Provider::<Http>::try_from("http://localhost:7545").unwrap()),
+            prestate_block,
+            poststate_block,
+        )
+    }
+
+    #[test]
+    fn new_rejects_empty_range() {
+        assert!(provider(10, 10).is_err());
+        assert!(provider(10, 5).is_err());
+        assert!(provider(10, 11).is_ok());
+    }
+
+    #[test]
+    fn block_at_maps_trace_indices() {
+        let provider = provider(100, 110).unwrap();
+        assert_eq!(provider.block_at(0), 101);
+        assert_eq!(provider.block_at(9), 110);
+        // Past the end of the range, the trace is padded with the poststate block.
+        assert_eq!(provider.block_at(10), 110);
+        assert_eq!(provider.block_at(u64::MAX), 110);
+    }
+
+    #[test]
+    fn serde_roundtrip_reconnects_lazily() {
+        let provider = provider(100, 110).unwrap();
+        let serialized = serde_json::to_string(&provider).unwrap();
+        let deserialized: OutputTraceProvider = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.rpc_url, provider.rpc_url);
+        assert_eq!(deserialized.block_at(0), 101);
+        assert!(deserialized.client().is_ok());
+    }
+
+    #[tokio::test]
+    async fn serves_outputs_from_the_node() {
+        let (node_provider, mock) = Provider::mocked();
+        let provider = OutputTraceProvider::new(Arc::new(node_provider), 100, 110).unwrap();
+        let output = OutputAtBlock {
+            version: H256::zero(),
+            output_root: H256::repeat_byte(0x01),
+            state_root: H256::repeat_byte(0x02),
+            withdrawal_storage_root: H256::repeat_byte(0x03),
+            block_ref: BlockRef {
+                hash: H256::repeat_byte(0x04),
+            },
+        };
+        mock.push::<OutputAtBlock, _>(output.clone()).unwrap();
+
+        assert_eq!(provider.claim_at(0).await.unwrap(), output.output_root);
+        mock.assert_request("optimism_outputAtBlock", vec!["0x65"])
+            .unwrap();
+
+        // The output at block 101 is cached, so no further requests are made.
+        assert_eq!(provider.state_at(0).await.unwrap(), output.output_root);
+        assert_eq!(
+            provider.preimage_at(0).await.unwrap(),
+            Bytes::from(
+                [
+                    output.version.as_bytes(),
+                    output.state_root.as_bytes(),
+                    output.withdrawal_storage_root.as_bytes(),
+                    output.block_ref.hash.as_bytes(),
+                ]
+                .concat()
+            )
+        );
+        assert!(mock.assert_request("optimism_outputAtBlock", ()).is_err());
+    }
+
+    #[tokio::test]
+    async fn surfaces_node_errors() {
+        let (node_provider, _mock) = Provider::mocked();
+        let provider = OutputTraceProvider::new(Arc::new(node_provider), 100, 110).unwrap();
+        assert!(provider.claim_at(0).await.is_err());
+        // Failed requests are not cached, so the output is requested again.
+        assert!(provider.state_at(0).await.is_err());
+    }
+}
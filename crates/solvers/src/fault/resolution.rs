@@ -0,0 +1,157 @@
+//! The resolution module contains the [Resolution] of a game's claim DAG, which predicts the
+//! outcome of the game and the time at which it can be resolved.
+
+use super::{ClaimData, GameStatus, Position};
+use anyhow::Result;
+
+/// The [Resolution] of a claim DAG, computed as the `FaultDisputeGame` contract would resolve it
+/// if no further moves were made.
+///
+/// Each claim is the root of a subgame. A claim at the maximum depth of the game is countered if
+/// it has been stepped against, and any other claim is countered if at least one of its children
+/// is uncountered. The challenger wins the game if the root claim is countered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolution {
+    /// Whether the claim at each index in the DAG array is countered within its subgame.
+    pub countered: Vec<bool>,
+    /// The predicted status of the game.
+    pub status: GameStatus,
+    /// The UNIX timestamp at which the chess clocks of every claim in the DAG have run out.
+    pub resolvable_at: u64,
+}
+
+impl Resolution {
+    /// Computes the [Resolution] of the given claim DAG.
+    ///
+    /// ### Takes
+    /// - `state`: The claim DAG. Every claim must come after its parent.
+    /// - `max_depth`: The maximum depth of the game tree.
+    /// - `game_duration`: The total duration of the game's chess clocks, in seconds.
+    ///
+    /// ### Returns
+    /// - `Ok(Resolution)`: The resolution of the claim DAG.
+    /// - `Err(anyhow::Error)`: The claim DAG is empty or malformed.
+    pub fn new(state: &[ClaimData], max_depth: u64, game_duration: u64) -> Result<Self> {
+        if state.is_empty() {
+            anyhow::bail!("Cannot resolve an empty claim DAG");
+        }
+
+        // Resolve the subgames from the leaves up. Claims are always appended after their
+        // parents, so iterating in reverse visits every child before its parent.
+        let mut countered = vec![false; state.len()];
+        let mut has_uncountered_child = vec![false; state.len()];
+        for (i, claim) in state.iter().enumerate().rev() {
+            countered[i] = if claim.position.depth() == max_depth {
+                claim.countered
+            } else {
+                has_uncountered_child[i]
+            };
+
+            if i > 0 && !countered[i] {
+                *has_uncountered_child
+                    .get_mut(claim.parent_index)
+                    .filter(|_| claim.parent_index < i)
+                    .ok_or(anyhow::anyhow!(
+                        "Claim at index {} has an invalid parent index {}",
+                        i,
+                        claim.parent_index
+                    ))? = true;
+            }
+        }
+
+        let resolvable_at = (0..state.len())
+            .map(|i| expires_at(state, i, game_duration))
+            .max()
+            .unwrap_or_default();
+
+        Ok(Self {
+            status: if countered[0] {
+                GameStatus::ChallengerWins
+            } else {
+                GameStatus::DefenderWins
+            },
+            countered,
+            resolvable_at,
+        })
+    }
+
+    /// Returns whether the game can be resolved at the given UNIX timestamp.
+    pub fn is_resolvable(&self, now: u64) -> bool {
+        now >= self.resolvable_at
+    }
+}
+
+/// Returns the UNIX timestamp at which the chess clock of the team that would counter the claim
+/// at the given index runs out. A claim can no longer be countered once the duration of its
+/// grandparent plus the time elapsed since it was made exceeds half of the game duration.
+pub fn expires_at(state: &[ClaimData], index: usize, game_duration: u64) -> u64 {
+    let Some(claim) = state.get(index) else {
+        return 0;
+    };
+    let grandparent_duration = state
+        .get(claim.parent_index)
+        .map(|parent| parent.clock.duration)
+        .unwrap_or_default();
+    (claim.clock.timestamp + (game_duration >> 1) + 1).saturating_sub(grandparent_duration)
+}
+
+#[cfg(test)]
+mod test {
+    use super::Resolution;
+    use crate::fault::{ClaimData, Clock, GameStatus};
+
+    /// Creates a new [ClaimData] with the given parent, position and clock.
+    fn claim(parent_index: usize, position: u128, countered: bool, clock: (u64, u64)) -> ClaimData {
+        ClaimData {
+            parent_index,
+            countered,
+            claim: Default::default(),
+            position,
+            clock: Clock {
+                duration: clock.0,
+                timestamp: clock.1,
+            },
+        }
+    }
+
+    #[test]
+    fn resolves_subgames() {
+        // An uncountered root resolves for the defender.
+        let state = vec![claim(u32::MAX as usize, 1, false, (0, 0))];
+        let resolution = Resolution::new(&state, 2, 100).unwrap();
+        assert_eq!(resolution.status, GameStatus::DefenderWins);
+        assert_eq!(resolution.resolvable_at, 51);
+
+        // The root is countered by the attack at index 3, whose own subgame is uncountered
+        // because the claim at index 4 has been stepped against.
+        let mut state = vec![
+            claim(u32::MAX as usize, 1, true, (0, 0)),
+            claim(0, 2, true, (10, 10)),
+            claim(1, 4, false, (5, 15)),
+            claim(0, 2, true, (20, 20)),
+            claim(3, 4, true, (10, 30)),
+        ];
+        let resolution = Resolution::new(&state, 2, 100).unwrap();
+        assert_eq!(resolution.countered, vec![true, true, false, false, true]);
+        assert_eq!(resolution.status, GameStatus::ChallengerWins);
+        assert_eq!(resolution.resolvable_at, 71);
+        assert!(!resolution.is_resolvable(70));
+        assert!(resolution.is_resolvable(71));
+
+        // Countering the attack at index 3 flips the outcome.
+        state.push(claim(3, 6, false, (15, 40)));
+        let resolution = Resolution::new(&state, 2, 100).unwrap();
+        assert_eq!(resolution.status, GameStatus::DefenderWins);
+        assert_eq!(resolution.resolvable_at, 71);
+    }
+
+    #[test]
+    fn rejects_malformed_dags() {
+        assert!(Resolution::new(&[], 2, 100).is_err());
+        let state = vec![
+            claim(u32::MAX as usize, 1, false, (0, 0)),
+            claim(2, 2, false, (0, 0)),
+        ];
+        assert!(Resolution::new(&state, 2, 100).is_err());
+    }
+}
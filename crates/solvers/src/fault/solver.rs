@@ -0,0 +1,209 @@
+//! The solver module contains the [FaultSolver], an implementation of the [FaultGame] trait that
+//! is generic over the [TraceProvider] that sources its trace.
+
+use super::{
+    providers::{AlphabetTraceProvider, CannonTraceProvider, OutputTraceProvider},
+    Claim, ClaimData, FaultGame, GameStatus, Position, TraceProvider, MAX_SUPPORTED_DEPTH,
+};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use ethers::types::{Address, Bytes};
+use serde::{Deserialize, Serialize};
+
+/// A [FaultSolver] for the alphabet fault dispute game.
+pub type AlphabetGame = FaultSolver<AlphabetTraceProvider>;
+
+/// A [FaultSolver] for execution trace games played over a Cannon-style VM.
+pub type CannonGame = FaultSolver<CannonTraceProvider>;
+
+/// A [FaultSolver] for output bisection games, where the trace is a sequence of trusted L2 output
+/// roots.
+pub type OutputGame = FaultSolver<OutputTraceProvider>;
+
+/// A struct containing information and the world state of a [op-challenger-driver::bindings::FaultDisputeGame].
+/// The [FaultSolver] is generic over the [TraceProvider] that sources the trace it commits to.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FaultSolver<P> {
+    /// The address of the dispute game contract.
+    pub address: Address,
+    /// The UNIX timestamp of the game's creation.
+    pub created_at: u64,
+    /// The current state of the game DAG.
+    pub state: Vec<ClaimData>,
+    /// The maximum depth of the game tree.
+    pub max_depth: u64,
+    /// The absolute prestate of the game's VM.
+    pub absolute_prestate: Claim,
+    /// The total duration of the game's chess clocks, in seconds.
+    pub game_duration: u64,
+    /// The source of our trace.
+    pub provider: P,
+    /// The status of the game, as last observed onchain.
+    #[serde(default)]
+    pub status: GameStatus,
+}
+
+#[async_trait]
+impl<T, P> FaultGame<T> for FaultSolver<P>
+where
+    T: Send,
+    P: TraceProvider<T>,
+{
+    fn claim_data(&self, index: usize) -> Result<&ClaimData> {
+        self.state.get(index).ok_or(anyhow!("Invalid claim index"))
+    }
+
+    async fn state_at(&self, position: u128) -> Result<T> {
+        self.provider.state_at(self.trace_index(position)?).await
+    }
+
+    async fn claim_at(&self, position: u128) -> Result<Claim> {
+        self.provider.claim_at(self.trace_index(position)?).await
+    }
+
+    fn max_depth(&self) -> u64 {
+        self.max_depth
+    }
+
+    fn game_duration(&self) -> u64 {
+        self.game_duration
+    }
+
+    fn state(&self) -> &[ClaimData] {
+        &self.state
+    }
+
+    fn trace_index(&self, position: u128) -> Result<u64> {
+        if position.depth() > self.max_depth {
+            anyhow::bail!("Position is deeper than the maximum game depth");
+        }
+        Ok(position.trace_index(self.max_depth))
+    }
+
+    async fn preimage_at(&self, position: u128) -> Result<Bytes> {
+        self.provider.preimage_at(self.trace_index(position)?).await
+    }
+
+    async fn proof_at(&self, position: u128) -> Result<Bytes> {
+        self.provider.proof_at(self.trace_index(position)?).await
+    }
+}
+
+impl<P> FaultSolver<P> {
+    /// Creates a new [FaultSolver] with an empty DAG from the game's onchain parameters.
+    ///
+    /// ### Takes
+    /// - `address`: The address of the dispute game contract.
+    /// - `created_at`: The UNIX timestamp of the game's creation.
+    /// - `max_depth`: The maximum depth of the game tree.
+    /// - `absolute_prestate`: The absolute prestate of the game's VM.
+    /// - `game_duration`: The total duration of the game's chess clocks, in seconds.
+    /// - `provider`: The source of our trace.
+    ///
+    /// ### Returns
+    /// - `Ok(FaultSolver)`: The new game.
+    /// - `Err(anyhow::Error)`: The game's parameters are not supported by the solver.
+    pub async fn new<T>(
+        address: Address,
+        created_at: u64,
+        max_depth: u64,
+        absolute_prestate: Claim,
+        game_duration: u64,
+        provider: P,
+    ) -> Result<Self>
+    where
+        P: TraceProvider<T>,
+    {
+        if max_depth > MAX_SUPPORTED_DEPTH {
+            anyhow::bail!(
+                "Unsupported max depth {}; the maximum supported depth is {}",
+                max_depth,
+                MAX_SUPPORTED_DEPTH
+            );
+        }
+        if game_duration == 0 {
+            anyhow::bail!("Unsupported game duration of 0 seconds");
+        }
+        let expected_prestate = provider.absolute_prestate().await?;
+        if absolute_prestate != expected_prestate {
+            anyhow::bail!(
+                "Unsupported absolute prestate {:?}; expected {:?}",
+                absolute_prestate,
+                expected_prestate
+            );
+        }
+
+        Ok(Self {
+            address,
+            created_at,
+            state: Vec::default(),
+            max_depth,
+            absolute_prestate,
+            game_duration,
+            provider,
+            status: GameStatus::InProgress,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::AlphabetGame;
+    use crate::fault::{
+        compute_gindex, providers::AlphabetTraceProvider, Claim, FaultGame, Position,
+        MAX_SUPPORTED_DEPTH,
+    };
+    use anyhow::Result;
+    use ethers::types::Address;
+    use std::sync::Arc;
+
+    const TRACE: [u8; 16] = [
+        16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31,
+    ];
+    const GAME_DURATION: u64 = 60 * 60 * 24 * 7;
+
+    /// Creates a new [AlphabetGame] with the given parameters.
+    async fn game(max_depth: u64, prestate: u64, game_duration: u64) -> Result<AlphabetGame> {
+        AlphabetGame::new(
+            Address::zero(),
+            0,
+            max_depth,
+            Claim::from_low_u64_be(prestate),
+            game_duration,
+            AlphabetTraceProvider::new(Arc::new(TRACE), max_depth)?,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn new_rejects_invalid_params() {
+        assert!(game(MAX_SUPPORTED_DEPTH + 1, 15, GAME_DURATION)
+            .await
+            .is_err());
+        assert!(game(4, 96, GAME_DURATION).await.is_err());
+        assert!(game(4, 15, 0).await.is_err());
+        assert!(game(4, 15, GAME_DURATION).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn state_at_production_depth() {
+        let game = game(MAX_SUPPORTED_DEPTH, 15, GAME_DURATION).await.unwrap();
+
+        let root: u128 = 1;
+        assert_eq!(root.trace_index(MAX_SUPPORTED_DEPTH), u64::MAX >> 1);
+        assert_eq!(game.state_at(root).await.unwrap(), 31);
+        assert_eq!(
+            game.state_at(compute_gindex(MAX_SUPPORTED_DEPTH as u8, 3))
+                .await
+                .unwrap(),
+            19
+        );
+        assert!(game.claim_at(root).await.is_ok());
+        // Positions deeper than the max depth are invalid.
+        assert!(game
+            .state_at(compute_gindex(MAX_SUPPORTED_DEPTH as u8 + 1, 0))
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,399 @@
+//! The split module contains the [SplitSolver], an implementation of the [FaultGame] trait for
+//! split-depth games. Positions at or above the split depth commit to the upper trace, and each
+//! subtree below the split depth commits to its own execution trace.
+
+use super::{
+    providers::{CannonTraceFactory, OutputTraceProvider},
+    BottomTraceFactory, Claim, ClaimData, FaultGame, GameStatus, Position, TraceProvider,
+    MAX_SUPPORTED_DEPTH,
+};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use ethers::types::{Address, Bytes};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+/// A [SplitSolver] for games that bisect output roots above the split depth and Cannon execution
+/// traces of single L2 blocks below it.
+pub type OutputCannonGame = SplitSolver<OutputTraceProvider, CannonTraceFactory>;
+
+/// A state within a split-depth game, from either the upper or the lower half of the game tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SplitState<A, B> {
+    /// A state from the upper trace.
+    Top(A),
+    /// A state from the execution trace of a subtree below the split depth.
+    Bottom(B),
+}
+
+/// The location of a [Position] within a split-depth game tree.
+enum SplitIndex {
+    /// The trace index within the upper trace.
+    Top(u64),
+    /// The trace index of the disputed upper trace state, and the trace index within the
+    /// execution trace of the subtree.
+    Bottom(u64, u64),
+}
+
+/// A struct containing information and the world state of a split-depth
+/// [op-challenger-driver::bindings::FaultDisputeGame]. The [SplitSolver] plays the upper half of
+/// the game over the `top` [TraceProvider], and switches to the execution traces created by the
+/// `factory` below the split depth.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitSolver<Top, F: BottomTraceFactory<Top>> {
+    /// The address of the dispute game contract.
+    pub address: Address,
+    /// The UNIX timestamp of the game's creation.
+    pub created_at: u64,
+    /// The current state of the game DAG.
+    pub state: Vec<ClaimData>,
+    /// The maximum depth of the game tree.
+    pub max_depth: u64,
+    /// The depth at which the game switches from the upper trace to the execution traces.
+    pub split_depth: u64,
+    /// The absolute prestate of the game's VM.
+    pub absolute_prestate: Claim,
+    /// The total duration of the game's chess clocks, in seconds.
+    pub game_duration: u64,
+    /// The source of the upper trace.
+    pub top: Top,
+    /// The factory for the execution traces below the split depth.
+    pub factory: F,
+    /// The status of the game, as last observed onchain.
+    #[serde(default)]
+    pub status: GameStatus,
+    /// The execution trace providers that have been created, keyed by the trace index of the
+    /// disputed upper trace state.
+    #[serde(skip)]
+    bottoms: Mutex<HashMap<u64, Arc<F::Provider>>>,
+}
+
+#[async_trait]
+impl<TT, Top, F> FaultGame<SplitState<TT, F::State>> for SplitSolver<Top, F>
+where
+    TT: Send,
+    Top: TraceProvider<TT>,
+    F: BottomTraceFactory<Top>,
+    F::State: Send,
+{
+    fn claim_data(&self, index: usize) -> Result<&ClaimData> {
+        self.state.get(index).ok_or(anyhow!("Invalid claim index"))
+    }
+
+    async fn state_at(&self, position: u128) -> Result<SplitState<TT, F::State>> {
+        match self.locate(position)? {
+            SplitIndex::Top(index) => Ok(SplitState::Top(self.top.state_at(index).await?)),
+            SplitIndex::Bottom(top_index, index) => Ok(SplitState::Bottom(
+                self.bottom(top_index)?.state_at(index).await?,
+            )),
+        }
+    }
+
+    async fn claim_at(&self, position: u128) -> Result<Claim> {
+        match self.locate(position)? {
+            SplitIndex::Top(index) => self.top.claim_at(index).await,
+            SplitIndex::Bottom(top_index, index) => self.bottom(top_index)?.claim_at(index).await,
+        }
+    }
+
+    fn max_depth(&self) -> u64 {
+        self.max_depth
+    }
+
+    fn game_duration(&self) -> u64 {
+        self.game_duration
+    }
+
+    fn state(&self) -> &[ClaimData] {
+        &self.state
+    }
+
+    fn trace_index(&self, position: u128) -> Result<u64> {
+        match self.locate(position)? {
+            SplitIndex::Top(index) | SplitIndex::Bottom(_, index) => Ok(index),
+        }
+    }
+
+    async fn preimage_at(&self, position: u128) -> Result<Bytes> {
+        match self.locate(position)? {
+            SplitIndex::Top(index) => self.top.preimage_at(index).await,
+            SplitIndex::Bottom(top_index, index) => {
+                self.bottom(top_index)?.preimage_at(index).await
+            }
+        }
+    }
+
+    async fn proof_at(&self, position: u128) -> Result<Bytes> {
+        match self.locate(position)? {
+            SplitIndex::Top(index) => self.top.proof_at(index).await,
+            SplitIndex::Bottom(top_index, index) => self.bottom(top_index)?.proof_at(index).await,
+        }
+    }
+}
+
+impl<Top, F: BottomTraceFactory<Top>> SplitSolver<Top, F> {
+    /// Creates a new [SplitSolver] with an empty DAG from the game's onchain parameters.
+    ///
+    /// ### Takes
+    /// - `address`: The address of the dispute game contract.
+    /// - `created_at`: The UNIX timestamp of the game's creation.
+    /// - `max_depth`: The maximum depth of the game tree.
+    /// - `split_depth`: The depth at which the game switches to the execution traces.
+    /// - `absolute_prestate`: The absolute prestate of the game's VM.
+    /// - `game_duration`: The total duration of the game's chess clocks, in seconds.
+    /// - `top`: The source of the upper trace.
+    /// - `factory`: The factory for the execution traces below the split depth.
+    ///
+    /// ### Returns
+    /// - `Ok(SplitSolver)`: The new game.
+    /// - `Err(anyhow::Error)`: The game's parameters are not supported by the solver.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn new(
+        address: Address,
+        created_at: u64,
+        max_depth: u64,
+        split_depth: u64,
+        absolute_prestate: Claim,
+        game_duration: u64,
+        top: Top,
+        factory: F,
+    ) -> Result<Self> {
+        if max_depth > MAX_SUPPORTED_DEPTH {
+            anyhow::bail!(
+                "Unsupported max depth {}; the maximum supported depth is {}",
+                max_depth,
+                MAX_SUPPORTED_DEPTH
+            );
+        }
+        if split_depth >= max_depth {
+            anyhow::bail!(
+                "Unsupported split depth {}; must be less than the max depth {}",
+                split_depth,
+                max_depth
+            );
+        }
+        if game_duration == 0 {
+            anyhow::bail!("Unsupported game duration of 0 seconds");
+        }
+
+        // The absolute prestate of the game is the absolute prestate of the VM, which is shared
+        // between all execution traces.
+        let expected_prestate = factory.provider_for(&top, 0)?.absolute_prestate().await?;
+        if absolute_prestate != expected_prestate {
+            anyhow::bail!(
+                "Unsupported absolute prestate {:?}; expected {:?}",
+                absolute_prestate,
+                expected_prestate
+            );
+        }
+
+        Ok(Self {
+            address,
+            created_at,
+            state: Vec::default(),
+            max_depth,
+            split_depth,
+            absolute_prestate,
+            game_duration,
+            top,
+            factory,
+            status: GameStatus::InProgress,
+            bottoms: Mutex::default(),
+        })
+    }
+
+    /// Locates the given [Position] within the upper trace or the execution trace of the subtree
+    /// it belongs to.
+    fn locate(&self, position: u128) -> Result<SplitIndex> {
+        if position.depth() > self.max_depth {
+            anyhow::bail!("Position is deeper than the maximum game depth");
+        }
+        if position.depth() <= self.split_depth {
+            return Ok(SplitIndex::Top(position.trace_index(self.split_depth)));
+        }
+
+        // The root of the subtree is a move against a claim at the split depth, and always a left
+        // child. An attack against the claim at trace index `i` is at index `2i`, and disputes
+        // the transition to the claim's trace index. A defense is at index `2(i + 1)`, and
+        // disputes the transition to the next trace index.
+        let subtree_depth = self.split_depth + 1;
+        let top_index = position.ancestor_at_depth(subtree_depth).index_at_depth() >> 1;
+        let index = position
+            .relative_to_ancestor_at_depth(subtree_depth)
+            .trace_index(self.max_depth - subtree_depth);
+        Ok(SplitIndex::Bottom(top_index, index))
+    }
+
+    /// Returns the execution trace provider for the subtree that disputes the transition to the
+    /// given upper trace index, creating it if necessary.
+    fn bottom(&self, top_index: u64) -> Result<Arc<F::Provider>> {
+        let mut bottoms = self
+            .bottoms
+            .lock()
+            .map_err(|_| anyhow!("Execution trace providers poisoned"))?;
+        if let Some(provider) = bottoms.get(&top_index) {
+            return Ok(Arc::clone(provider));
+        }
+        let provider = Arc::new(self.factory.provider_for(&self.top, top_index)?);
+        bottoms.insert(top_index, Arc::clone(&provider));
+        Ok(provider)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SplitSolver, SplitState};
+    use crate::fault::{
+        compute_gindex, providers::AlphabetTraceProvider, BottomTraceFactory, Claim, FaultGame,
+        FaultGameEmulator, Position, Response, TraceProvider,
+    };
+    use anyhow::Result;
+    use ethers::types::{Address, Bytes};
+    use serde::{Deserialize, Serialize};
+    use std::sync::Arc;
+
+    const SPLIT_DEPTH: u64 = 2;
+    const MAX_DEPTH: u64 = 4;
+
+    /// Creates alphabet execution traces of length 2 that start at `10 * (top_index + 1)`.
+    #[derive(Serialize, Deserialize)]
+    struct AlphabetFactory;
+
+    impl BottomTraceFactory<AlphabetTraceProvider> for AlphabetFactory {
+        type State = u8;
+        type Provider = AlphabetTraceProvider;
+
+        fn provider_for(
+            &self,
+            _: &AlphabetTraceProvider,
+            top_index: u64,
+        ) -> Result<Self::Provider> {
+            let start = 10 * (top_index as u8 + 1);
+            AlphabetTraceProvider::new(Arc::new([start, start + 1]), MAX_DEPTH - SPLIT_DEPTH - 1)
+        }
+    }
+
+    /// Creates a new [SplitSolver] with the given depths.
+    async fn game(
+        max_depth: u64,
+        split_depth: u64,
+    ) -> Result<SplitSolver<AlphabetTraceProvider, AlphabetFactory>> {
+        SplitSolver::new(
+            Address::zero(),
+            0,
+            max_depth,
+            split_depth,
+            Claim::from_low_u64_be(9),
+            60,
+            AlphabetTraceProvider::new(Arc::new([1, 2, 3, 4]), split_depth)?,
+            AlphabetFactory,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn new_rejects_invalid_split_depth() {
+        assert!(game(MAX_DEPTH, MAX_DEPTH).await.is_err());
+        assert!(game(MAX_DEPTH, SPLIT_DEPTH).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn routes_positions_to_traces() {
+        let game = game(MAX_DEPTH, SPLIT_DEPTH).await.unwrap();
+        let top = AlphabetTraceProvider::new(Arc::new([1, 2, 3, 4]), SPLIT_DEPTH).unwrap();
+
+        // Positions at or above the split depth commit to the upper trace.
+        assert_eq!(game.state_at(1).await.unwrap(), SplitState::Top(4));
+        assert_eq!(
+            game.state_at(compute_gindex(2, 1)).await.unwrap(),
+            SplitState::Top(2)
+        );
+        assert_eq!(
+            game.claim_at(compute_gindex(2, 1)).await.unwrap(),
+            top.claim_at(1).await.unwrap()
+        );
+
+        // An attack against the claim at split depth index 2 disputes the transition to upper
+        // trace index 2.
+        let attack_root = compute_gindex(2, 2).make_move(true);
+        assert_eq!(attack_root, compute_gindex(3, 4));
+        assert_eq!(game.trace_index(attack_root).unwrap(), 1);
+        assert_eq!(
+            game.state_at(attack_root).await.unwrap(),
+            SplitState::Bottom(31)
+        );
+        let leaf = attack_root.make_move(true);
+        assert_eq!(game.trace_index(leaf).unwrap(), 0);
+        assert_eq!(game.state_at(leaf).await.unwrap(), SplitState::Bottom(30));
+
+        // A defense of the claim at split depth index 2 disputes the transition to upper trace
+        // index 3.
+        let defense_root = compute_gindex(2, 2).make_move(false);
+        assert_eq!(defense_root, compute_gindex(3, 6));
+        assert_eq!(
+            game.state_at(defense_root).await.unwrap(),
+            SplitState::Bottom(41)
+        );
+        assert_eq!(
+            game.state_at(defense_root.make_move(true)).await.unwrap(),
+            SplitState::Bottom(40)
+        );
+
+        // A defense of the claim at split depth index 0 disputes the same transition as an
+        // attack against the claim at split depth index 1.
+        let defense_root = compute_gindex(2, 0).make_move(false);
+        assert_eq!(defense_root, compute_gindex(2, 1).make_move(true));
+        assert_eq!(
+            game.state_at(defense_root).await.unwrap(),
+            SplitState::Bottom(21)
+        );
+
+        // Positions below the max depth are invalid.
+        assert!(game.state_at(compute_gindex(5, 0)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn responds_across_the_split_depth() {
+        let mut game = game(MAX_DEPTH, SPLIT_DEPTH).await.unwrap();
+        let mut emulator = FaultGameEmulator::new(
+            Claim::repeat_byte(0xff),
+            MAX_DEPTH,
+            Claim::from_low_u64_be(9),
+            60,
+            0,
+        );
+        game.state = emulator.claim_data.clone();
+
+        // We attack the dishonest root claim, and the opponent counters us at the split depth.
+        let Response::Move(true, claim, None) = game.respond(0).await.unwrap() else {
+            panic!("Expected an attack against the root claim");
+        };
+        emulator.attack(0, claim).unwrap();
+        emulator.attack(1, Claim::repeat_byte(0xff)).unwrap();
+        game.state = emulator.claim_data.clone();
+
+        // The attack against the claim at the split depth is the root of the execution trace
+        // subtree that disputes the transition to upper trace index 0, and commits to the last
+        // state of that execution trace.
+        let bottom = AlphabetFactory.provider_for(&game.top, 0).unwrap();
+        let Response::Move(true, claim, None) = game.respond(2).await.unwrap() else {
+            panic!("Expected an attack against the claim at the split depth");
+        };
+        assert_eq!(claim, bottom.claim_at(1).await.unwrap());
+        emulator.attack(2, claim).unwrap();
+        assert_eq!(emulator.claim_data[3].position, compute_gindex(3, 0));
+
+        // Once the opponent counters us at the max depth, we step against the first state of the
+        // execution trace, from the absolute prestate.
+        emulator.attack(3, Claim::repeat_byte(0xff)).unwrap();
+        game.state = emulator.claim_data.clone();
+        assert_eq!(
+            game.respond(4).await.unwrap(),
+            Response::Step(0, 4, true, Bytes::default(), Bytes::default())
+        );
+    }
+}
@@ -9,7 +9,7 @@ pub type Claim = H256;
 
 /// The [Clock] struct represents a clock that is used to track the duration and timestamp of a
 /// given [Claim] within the game.
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Clock {
     /// The duration remaining on the chess clock.
@@ -19,7 +19,7 @@ pub struct Clock {
 }
 
 /// The [ClaimData] struct represents a [Claim] as well as the data associated with it.
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ClaimData {
     /// The index of the parent claim in the DAG array.
@@ -36,6 +36,7 @@ pub struct ClaimData {
 
 /// A [Response] is an action taken by a participant in the dispute game in response to
 /// a claim made by another participant.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Response {
     /// Do nothing.
     DoNothing,
@@ -44,3 +45,73 @@ pub enum Response {
     /// Perform a VM step against the parent claim.
     Step(usize, usize, bool, Bytes, Bytes),
 }
+
+/// The [GameStatus] enum represents the status of a dispute game, as defined by the
+/// `FaultDisputeGame` contract.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum GameStatus {
+    /// The game is still in progress.
+    #[default]
+    InProgress = 0,
+    /// The root claim was countered; the challenger wins.
+    ChallengerWins = 1,
+    /// The root claim was not countered; the defender wins.
+    DefenderWins = 2,
+}
+
+impl TryFrom<u8> for GameStatus {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(GameStatus::InProgress),
+            1 => Ok(GameStatus::ChallengerWins),
+            2 => Ok(GameStatus::DefenderWins),
+            _ => Err(anyhow::anyhow!("Invalid game status")),
+        }
+    }
+}
+
+/// The [GameError] enum represents the custom errors that the `FaultDisputeGame` contract reverts
+/// with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameError {
+    /// The game has already been resolved.
+    GameNotInProgress,
+    /// The clock of the team making the move has run out.
+    ClockTimeExceeded,
+    /// The game can not be resolved while clocks are still running.
+    ClockNotExpired,
+    /// The root claim can not be defended.
+    CannotDefendRootClaim,
+    /// An identical claim already exists at the same position.
+    ClaimAlreadyExists,
+    /// The move would exceed the maximum depth of the game.
+    GameDepthExceeded,
+    /// The parent claim does not exist or can not be moved against.
+    InvalidParent,
+    /// The prestate passed to `step` does not match the claimed prestate.
+    InvalidPrestate,
+    /// The step's post state matches the claimed post state, so the claim is valid.
+    ValidStep,
+}
+
+impl std::fmt::Display for GameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            GameError::GameNotInProgress => "GameNotInProgress",
+            GameError::ClockTimeExceeded => "ClockTimeExceeded",
+            GameError::ClockNotExpired => "ClockNotExpired",
+            GameError::CannotDefendRootClaim => "CannotDefendRootClaim",
+            GameError::ClaimAlreadyExists => "ClaimAlreadyExists",
+            GameError::GameDepthExceeded => "GameDepthExceeded",
+            GameError::InvalidParent => "InvalidParent",
+            GameError::InvalidPrestate => "InvalidPrestate",
+            GameError::ValidStep => "ValidStep",
+        };
+        write!(f, "{}()", name)
+    }
+}
+
+impl std::error::Error for GameError {}